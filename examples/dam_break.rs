@@ -0,0 +1,58 @@
+use conlaw::{self, bc, cl, fluxes, methods, Domain, Driver, Problem, Resolution, Simulation};
+use std::{fs, io};
+
+fn main() {
+    tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .init();
+
+    let problem_name = "dam_break";
+
+    // dam break flooding a dry bump between two walls
+    let bathymetry = |x: f64| {
+        if x.abs() < 0.2 {
+            0.4 - 2. * x.abs()
+        } else {
+            0.
+        }
+    };
+    let shallow_water = cl::ShallowWater::default();
+
+    let problem = Problem::new(
+        problem_name,
+        shallow_water,
+        Domain {
+            time: (0., 1.),
+            space: (-1., 1.),
+        },
+        bc::Reflective::new(2, [1]),
+        move |x, mut v| {
+            let surface = if x < -0.5 { 0.5 } else { 0. };
+            let depth = surface - bathymetry(x);
+            v[(0, 0)] = if depth > 0. { depth } else { 0. };
+            v[(1, 0)] = 0.;
+        },
+    );
+
+    let sim = Simulation::new(problem)
+        .with_method_instance(methods::HydrostaticReconstruction::new(
+            shallow_water,
+            fluxes::Hll,
+            bathymetry,
+        ))
+        .with_time_resolution(Resolution::Delta(0.0002))
+        .with_space_resolution(Resolution::Delta(0.002));
+
+    let mut output = io::BufWriter::new(
+        fs::File::create(format!("bin/{}.csff1", problem_name))
+            .expect("couldn't create output file"),
+    );
+
+    Driver::new(sim)
+        .with_observer(conlaw::Logger)
+        .with_observer(conlaw::Csff1Writer::new(&mut output))
+        .with_time_sampling(Resolution::Steps(10))
+        .with_space_sampling(Resolution::Steps(2))
+        .run()
+        .expect("failed to run simulation");
+}
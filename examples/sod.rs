@@ -0,0 +1,51 @@
+use conlaw::{self, bc, cl, fluxes, methods, Domain, Driver, Problem, Resolution, Simulation};
+use std::{fs, io};
+
+fn main() {
+    tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .init();
+
+    let problem_name = "sod";
+
+    let euler = cl::Euler::<f64>::default();
+    let left = cl::Primitive {
+        density: 1.,
+        velocity: 0.,
+        pressure: 1.,
+    };
+    let right = cl::Primitive {
+        density: 0.125,
+        velocity: 0.,
+        pressure: 0.1,
+    };
+
+    let problem = Problem::new(
+        problem_name,
+        euler,
+        Domain {
+            time: (0., 0.2),
+            space: (0., 1.),
+        },
+        bc::Outflow::constant(),
+        move |x, v| euler.conserved(if x < 0.5 { &left } else { &right }, v),
+    );
+
+    let sim = Simulation::new(problem)
+        .with_method_instance(methods::FiniteVolume::new(fluxes::Hllc))
+        .with_time_resolution(Resolution::Delta(0.0002))
+        .with_space_resolution(Resolution::Delta(0.001));
+
+    let mut output = io::BufWriter::new(
+        fs::File::create(format!("bin/{}.csff1", problem_name))
+            .expect("couldn't create output file"),
+    );
+
+    Driver::new(sim)
+        .with_observer(conlaw::Logger)
+        .with_observer(conlaw::Csff1Writer::new(&mut output))
+        .with_time_sampling(Resolution::Steps(5))
+        .with_space_sampling(Resolution::Steps(2))
+        .run()
+        .expect("failed to run simulation");
+}
@@ -1,27 +1,134 @@
 use std::marker::PhantomData;
 
-use faer_core::{MatMut, MatRef};
+use faer_core::{zipped, Mat, MatMut, MatRef};
 
-use crate::{problem::BoundaryCondition, Ctx, SimpleFloat};
+use crate::{
+    problem::{BoundaryCondition, OneSidedBoundaryCondition, Side},
+    Ctx, SimpleFloat,
+};
 
 pub struct Periodic;
 
 impl<F: SimpleFloat> BoundaryCondition<F> for Periodic {
-    fn apply(&self, _ctx: Ctx<F>, mut left: MatMut<F>, center: MatRef<F>, mut right: MatMut<F>) {
-        let corresponding = center.subrows(center.nrows() - left.nrows() - 1, left.nrows());
+    fn apply(&self, ctx: Ctx<F>, mut left: MatMut<F>, center: MatRef<F>, mut right: MatMut<F>) {
+        // first and last cells are the same point of the periodic domain
+        let corresponding = center.subrows(
+            center.nrows() - left.nrows() - ctx.system_size,
+            left.nrows(),
+        );
         left.clone_from(corresponding);
 
-        let corresponding = center.subrows(0, right.nrows());
+        let corresponding = center.subrows(ctx.system_size, right.nrows());
         right.clone_from(corresponding);
     }
 }
 
-pub struct Dirichlet<F, L, R> {
-    pub(crate) _left: L,
-    pub(crate) _right: R,
+/// Combines two one-sided boundary conditions into a boundary condition for the whole domain
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Split<L, R> {
+    pub left: L,
+    pub right: R,
+}
+
+impl<L, R> Split<L, R> {
+    pub fn new(left: L, right: R) -> Self {
+        Self { left, right }
+    }
+}
+
+impl<F, L, R> BoundaryCondition<F> for Split<L, R>
+where
+    F: SimpleFloat,
+    L: OneSidedBoundaryCondition<F>,
+    R: OneSidedBoundaryCondition<F>,
+{
+    fn apply(&self, ctx: Ctx<F>, left: MatMut<F>, center: MatRef<F>, right: MatMut<F>) {
+        self.left.apply_side(ctx, Side::Left, left, center);
+        self.right.apply_side(ctx, Side::Right, right, center);
+    }
+}
+
+/// Applies a one-sided boundary condition on both ends of the domain
+fn apply_both_sides<F: SimpleFloat>(
+    bc: &impl OneSidedBoundaryCondition<F>,
+    ctx: Ctx<F>,
+    left: MatMut<F>,
+    center: MatRef<F>,
+    right: MatMut<F>,
+) {
+    bc.apply_side(ctx, Side::Left, left, center);
+    bc.apply_side(ctx, Side::Right, right, center);
+}
+
+/// Iterates over the ghost cells of `ghost` along with their distance to the boundary cell
+fn ghost_cells<'a, F: SimpleFloat>(
+    ctx: Ctx<F>,
+    side: Side,
+    ghost: MatMut<'a, F>,
+) -> impl Iterator<Item = (usize, MatMut<'a, F>)> {
+    let cells = ghost.nrows() / ctx.system_size;
+    ghost
+        .into_row_chunks(ctx.system_size)
+        .enumerate()
+        .map(move |(k, g)| match side {
+            Side::Left => (cells - k, g),
+            Side::Right => (k + 1, g),
+        })
+}
+
+/// Returns the interior cell at distance `k` from the boundary cell of `side`
+fn interior_cell<'a, F: SimpleFloat>(
+    ctx: Ctx<F>,
+    side: Side,
+    center: MatRef<'a, F>,
+    k: usize,
+) -> MatRef<'a, F> {
+    let m = ctx.system_size;
+    match side {
+        Side::Left => center.subrows(k * m, m),
+        Side::Right => center.subrows(center.nrows() - (k + 1) * m, m),
+    }
+}
+
+/// Prescribes the value of every ghost cell of one side, `value` receiving the current time and a
+/// chunk of `system_size` rows to fill.
+pub struct Inflow<F, G> {
+    pub(crate) value: G,
     _marker: PhantomData<F>,
 }
 
+impl<F, G> Inflow<F, G>
+where
+    F: SimpleFloat,
+    G: Fn(F, MatMut<F>),
+{
+    pub fn new(value: G) -> Self {
+        Self {
+            value,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F, G> OneSidedBoundaryCondition<F> for Inflow<F, G>
+where
+    F: SimpleFloat,
+    G: Fn(F, MatMut<F>),
+{
+    fn apply_side(&self, ctx: Ctx<F>, _side: Side, ghost: MatMut<F>, _center: MatRef<F>) {
+        for ghost in ghost.into_row_chunks(ctx.system_size) {
+            (self.value)(ctx.t, ghost)
+        }
+    }
+}
+
+/// Prescribes the value of every ghost cell, with `left` and `right` receiving the current time
+/// and a chunk of `system_size` rows to fill.
+pub struct Dirichlet<F, L, R> {
+    pub(crate) left: Inflow<F, L>,
+    pub(crate) right: Inflow<F, R>,
+}
+
 impl<F, L, R> Dirichlet<F, L, R>
 where
     F: SimpleFloat,
@@ -30,9 +137,8 @@ where
 {
     pub fn new(left: L, right: R) -> Self {
         Self {
-            _left: left,
-            _right: right,
-            _marker: PhantomData,
+            left: Inflow::new(left),
+            right: Inflow::new(right),
         }
     }
 }
@@ -43,7 +149,207 @@ where
     L: Fn(F, MatMut<F>),
     R: Fn(F, MatMut<F>),
 {
-    fn apply(&self, _ctx: Ctx<F>, _left: MatMut<F>, _center: MatRef<F>, _right: MatMut<F>) {
-        todo!()
+    fn apply(&self, ctx: Ctx<F>, left: MatMut<F>, center: MatRef<F>, right: MatMut<F>) {
+        self.left.apply_side(ctx, Side::Left, left, center);
+        self.right.apply_side(ctx, Side::Right, right, center);
+    }
+}
+
+/// How [`Outflow`] fills ghost cells from the interior
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Extrapolation {
+    /// Copies the boundary cell (zero gradient)
+    #[default]
+    Constant,
+    /// Continues the slope between the two outermost interior cells, or copies the boundary cell
+    /// on a mesh of a single cell
+    Linear,
+}
+
+/// Transmissive boundary condition letting waves leave the domain
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Outflow {
+    extrapolation: Extrapolation,
+}
+
+impl Outflow {
+    pub fn new(extrapolation: Extrapolation) -> Self {
+        Self { extrapolation }
+    }
+
+    pub fn constant() -> Self {
+        Self::new(Extrapolation::Constant)
+    }
+
+    pub fn linear() -> Self {
+        Self::new(Extrapolation::Linear)
+    }
+}
+
+impl<F: SimpleFloat> OneSidedBoundaryCondition<F> for Outflow {
+    fn apply_side(&self, ctx: Ctx<F>, side: Side, ghost: MatMut<F>, center: MatRef<F>) {
+        let boundary = interior_cell(ctx, side, center, 0);
+        let interior = if center.nrows() > ctx.system_size {
+            interior_cell(ctx, side, center, 1)
+        } else {
+            boundary
+        };
+
+        for (k, ghost) in ghost_cells(ctx, side, ghost) {
+            match self.extrapolation {
+                Extrapolation::Constant => {
+                    zipped!(ghost, boundary).for_each(|mut g, b| g.write(b.read()))
+                }
+                Extrapolation::Linear => {
+                    let k = F::from_f64(k as f64);
+                    zipped!(ghost, boundary, interior).for_each(|mut g, b, i| {
+                        g.write(b.read().add(k.mul(b.read().sub(i.read()))))
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl<F: SimpleFloat> BoundaryCondition<F> for Outflow {
+    fn apply(&self, ctx: Ctx<F>, left: MatMut<F>, center: MatRef<F>, right: MatMut<F>) {
+        apply_both_sides(self, ctx, left, center, right)
+    }
+}
+
+/// Solid wall located on the boundary cells: ghost cells mirror the interior, with the components
+/// listed in `odd` (e.g. momentum) changing sign
+///
+/// The mesh needs more cells than the method has ghost cells on each side.
+#[derive(Debug, Clone)]
+pub struct Reflective {
+    system_size: usize,
+    odd: Vec<usize>,
+}
+
+impl Reflective {
+    /// Wall for a system of `system_size` components
+    ///
+    /// # Panics
+    ///
+    /// Panics if a component listed in `odd` does not exist in the system.
+    pub fn new(system_size: usize, odd: impl IntoIterator<Item = usize>) -> Self {
+        let odd: Vec<usize> = odd.into_iter().collect();
+        if let Some(c) = odd.iter().find(|&&c| c >= system_size) {
+            panic!(
+                "component {c} changing sign at the wall is not in a system of size {system_size}"
+            );
+        }
+        Self { system_size, odd }
+    }
+}
+
+impl<F: SimpleFloat> OneSidedBoundaryCondition<F> for Reflective {
+    fn apply_side(&self, ctx: Ctx<F>, side: Side, ghost: MatMut<F>, center: MatRef<F>) {
+        assert_eq!(
+            ctx.system_size, self.system_size,
+            "reflective wall set up for another system size"
+        );
+        let (ghosts, cells) = (ghost.nrows(), center.nrows());
+        assert!(
+            ghosts < cells,
+            "reflective wall needs more than {} cells to mirror, the mesh has {}",
+            ghosts / ctx.system_size,
+            cells / ctx.system_size
+        );
+
+        for (k, mut ghost) in ghost_cells(ctx, side, ghost) {
+            let mirror = interior_cell(ctx, side, center, k);
+            ghost.clone_from(mirror);
+            for &c in &self.odd {
+                ghost.write(c, 0, mirror.read(c, 0).neg());
+            }
+        }
+    }
+}
+
+impl<F: SimpleFloat> BoundaryCondition<F> for Reflective {
+    fn apply(&self, ctx: Ctx<F>, left: MatMut<F>, center: MatRef<F>, right: MatMut<F>) {
+        apply_both_sides(self, ctx, left, center, right)
+    }
+}
+
+/// Non-reflecting boundary condition based on the characteristic decomposition of the flux
+/// Jacobian at the boundary cell: outgoing characteristic values are extrapolated from the
+/// interior, incoming ones are taken from the far-field state given by `farfield`, which
+/// receives the current time and a chunk of `system_size` rows to fill.
+///
+/// Relies on [`ConservationLaw::eigen_decomposition`](crate::ConservationLaw::eigen_decomposition),
+/// and falls back to [`Outflow::constant`] where the flux Jacobian has no decomposition, such as
+/// at a dry shallow-water cell.
+pub struct Characteristic<F, G> {
+    pub(crate) farfield: G,
+    _marker: PhantomData<F>,
+}
+
+impl<F, G> Characteristic<F, G>
+where
+    F: SimpleFloat,
+    G: Fn(F, MatMut<F>),
+{
+    pub fn new(farfield: G) -> Self {
+        Self {
+            farfield,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F, G> OneSidedBoundaryCondition<F> for Characteristic<F, G>
+where
+    F: SimpleFloat,
+    G: Fn(F, MatMut<F>),
+{
+    fn apply_side(&self, ctx: Ctx<F>, side: Side, ghost: MatMut<F>, center: MatRef<F>) {
+        let m = ctx.system_size;
+        let boundary = interior_cell(ctx, side, center, 0);
+        let Some(eigen) = ctx.problem.cl.eigen_decomposition(boundary) else {
+            return Outflow::constant().apply_side(ctx, side, ghost, center);
+        };
+
+        let mut farfield = Mat::<F>::zeros(m, 1);
+        (self.farfield)(ctx.t, farfield.as_mut());
+
+        // characteristic variables of the ghost state
+        let mut w = Mat::<F>::zeros(m, 1);
+        for i in 0..m {
+            let incoming = match side {
+                Side::Left => eigen.values.read(i, 0) > F::zero(),
+                Side::Right => eigen.values.read(i, 0) < F::zero(),
+            };
+            let state = if incoming {
+                farfield.as_ref()
+            } else {
+                boundary
+            };
+            let wi = (0..m).fold(F::zero(), |acc, j| {
+                acc.add(eigen.left.read(i, j).mul(state.read(j, 0)))
+            });
+            w.write(i, 0, wi);
+        }
+
+        for mut ghost in ghost.into_row_chunks(m) {
+            for j in 0..m {
+                let uj = (0..m).fold(F::zero(), |acc, i| {
+                    acc.add(eigen.right.read(j, i).mul(w.read(i, 0)))
+                });
+                ghost.write(j, 0, uj);
+            }
+        }
+    }
+}
+
+impl<F, G> BoundaryCondition<F> for Characteristic<F, G>
+where
+    F: SimpleFloat,
+    G: Fn(F, MatMut<F>),
+{
+    fn apply(&self, ctx: Ctx<F>, left: MatMut<F>, center: MatRef<F>, right: MatMut<F>) {
+        apply_both_sides(self, ctx, left, center, right)
     }
 }
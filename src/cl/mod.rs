@@ -0,0 +1,103 @@
+use std::marker::PhantomData;
+
+use faer_core::{zipped, Mat, MatMut, MatRef};
+
+use crate::{linalg, ConservationLaw, EigenDecomposition, SimpleFloat};
+
+mod euler;
+pub mod models;
+mod shallow_water;
+
+pub use euler::*;
+pub use shallow_water::*;
+
+pub struct General<F, G> {
+    system_size: usize,
+    flux_function: G,
+    _marker: PhantomData<F>,
+}
+
+impl<F: SimpleFloat, G: Fn(MatRef<F>, MatMut<F>)> General<F, G> {
+    pub fn new(system_size: usize, flux_function: G) -> Self {
+        Self {
+            system_size,
+            flux_function,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: SimpleFloat, G: Fn(MatRef<F>, MatMut<F>)> ConservationLaw<F> for General<F, G> {
+    #[inline]
+    fn system_size(&self) -> usize {
+        self.system_size
+    }
+
+    #[inline]
+    fn flux_function(&self, u: MatRef<F>, v: MatMut<F>) {
+        (self.flux_function)(u, v)
+    }
+}
+
+pub struct Scalar<F, G> {
+    flux_function: G,
+    _marker: PhantomData<F>,
+}
+
+impl<F: SimpleFloat, G: Fn(F) -> F> Scalar<F, G> {
+    pub fn new(flux_function: G) -> Self {
+        Self {
+            flux_function,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Evaluates the flux at `u`
+    #[inline]
+    pub fn flux(&self, u: F) -> F {
+        (self.flux_function)(u)
+    }
+
+    /// Central difference approximation of `f'(u)`
+    pub fn derivative(&self, u: F) -> F {
+        let h = linalg::difference_step(u);
+        self.flux(u.add(h)).sub(self.flux(u.sub(h))).div(h.add(h))
+    }
+}
+
+impl<F: SimpleFloat, G: Fn(F) -> F> ConservationLaw<F> for Scalar<F, G> {
+    #[inline]
+    fn system_size(&self) -> usize {
+        1
+    }
+
+    #[inline]
+    fn flux_function(&self, u: MatRef<F>, v: MatMut<F>) {
+        zipped!(v, u).for_each(|mut v, u| v.write((self.flux_function)(u.read())));
+    }
+
+    #[inline]
+    fn bulk_flux_function(&self, u: MatRef<F>, v: MatMut<F>) {
+        zipped!(v, u).for_each(|mut v, u| v.write((self.flux_function)(u.read())));
+    }
+
+    fn jacobian(&self, u: MatRef<F>, mut jac: MatMut<F>) {
+        jac.write(0, 0, self.derivative(u.read(0, 0)));
+    }
+
+    fn eigenvalues(&self, u: MatRef<F>, mut values: MatMut<F>) {
+        values.write(0, 0, self.derivative(u.read(0, 0)));
+    }
+
+    fn eigen_decomposition(&self, u: MatRef<F>) -> Option<EigenDecomposition<F>> {
+        Some(EigenDecomposition {
+            values: Mat::from_fn(1, 1, |_, _| self.derivative(u.read(0, 0))),
+            right: Mat::from_fn(1, 1, |_, _| F::one()),
+            left: Mat::from_fn(1, 1, |_, _| F::one()),
+        })
+    }
+
+    fn max_wave_speed(&self, u: MatRef<F>) -> F {
+        linalg::abs(self.derivative(u.read(0, 0)))
+    }
+}
@@ -0,0 +1,619 @@
+//! Standard conservation laws, with their wave speeds and, where known, the exact solution of
+//! their Riemann problems (see [`riemann::Exact`](crate::riemann::Exact) for the corresponding
+//! Godunov scheme).
+
+use std::marker::PhantomData;
+
+use faer_core::{Mat, MatMut, MatRef};
+
+use crate::{
+    linalg,
+    riemann::{self, ExactRiemannSolution},
+    ConservationLaw, EigenDecomposition, SimpleFloat,
+};
+
+/// Eigen-structure of a scalar conservation law of characteristic speed `speed`
+fn scalar_decomposition<F: SimpleFloat>(speed: F) -> EigenDecomposition<F> {
+    EigenDecomposition {
+        values: Mat::from_fn(1, 1, |_, _| speed),
+        right: Mat::from_fn(1, 1, |_, _| F::one()),
+        left: Mat::from_fn(1, 1, |_, _| F::one()),
+    }
+}
+
+/// Linear advection `u_t + a u_x = 0`
+#[derive(Debug, Clone, Copy)]
+pub struct Advection<F> {
+    a: F,
+}
+
+impl<F: SimpleFloat> Default for Advection<F> {
+    /// Unit speed
+    fn default() -> Self {
+        Self::new(F::one())
+    }
+}
+
+impl<F: SimpleFloat> Advection<F> {
+    pub fn new(a: F) -> Self {
+        Self { a }
+    }
+
+    pub fn speed(&self) -> F {
+        self.a
+    }
+}
+
+impl<F: SimpleFloat> ConservationLaw<F> for Advection<F> {
+    #[inline]
+    fn system_size(&self) -> usize {
+        1
+    }
+
+    fn flux_function(&self, u: MatRef<F>, mut v: MatMut<F>) {
+        v.write(0, 0, self.a.mul(u.read(0, 0)));
+    }
+
+    fn jacobian(&self, _u: MatRef<F>, mut jac: MatMut<F>) {
+        jac.write(0, 0, self.a);
+    }
+
+    fn eigenvalues(&self, _u: MatRef<F>, mut values: MatMut<F>) {
+        values.write(0, 0, self.a);
+    }
+
+    fn eigen_decomposition(&self, _u: MatRef<F>) -> Option<EigenDecomposition<F>> {
+        Some(scalar_decomposition(self.a))
+    }
+
+    fn max_wave_speed(&self, _u: MatRef<F>) -> F {
+        linalg::abs(self.a)
+    }
+}
+
+impl<F: SimpleFloat> ExactRiemannSolution<F> for Advection<F> {
+    fn riemann_sample(&self, left: MatRef<F>, right: MatRef<F>, xi: F, mut u: MatMut<F>) {
+        let side = if xi < self.a { left } else { right };
+        u.write(0, 0, side.read(0, 0));
+    }
+}
+
+/// Inviscid Burgers equation `u_t + (u²/2)_x = 0`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Burgers;
+
+impl<F: SimpleFloat> ConservationLaw<F> for Burgers {
+    #[inline]
+    fn system_size(&self) -> usize {
+        1
+    }
+
+    fn flux_function(&self, u: MatRef<F>, mut v: MatMut<F>) {
+        let u = u.read(0, 0);
+        v.write(0, 0, u.mul(u).mul(F::from_f64(0.5)));
+    }
+
+    fn jacobian(&self, u: MatRef<F>, mut jac: MatMut<F>) {
+        jac.write(0, 0, u.read(0, 0));
+    }
+
+    fn eigenvalues(&self, u: MatRef<F>, mut values: MatMut<F>) {
+        values.write(0, 0, u.read(0, 0));
+    }
+
+    fn eigen_decomposition(&self, u: MatRef<F>) -> Option<EigenDecomposition<F>> {
+        Some(scalar_decomposition(u.read(0, 0)))
+    }
+
+    fn max_wave_speed(&self, u: MatRef<F>) -> F {
+        linalg::abs(u.read(0, 0))
+    }
+}
+
+impl<F: SimpleFloat> ExactRiemannSolution<F> for Burgers {
+    fn riemann_sample(&self, left: MatRef<F>, right: MatRef<F>, xi: F, mut u: MatMut<F>) {
+        let (ul, ur) = (left.read(0, 0), right.read(0, 0));
+        let value = if ul > ur {
+            // shock of speed (u_L + u_R)/2
+            if xi < ul.add(ur).mul(F::from_f64(0.5)) {
+                ul
+            } else {
+                ur
+            }
+        } else if xi <= ul {
+            ul
+        } else if xi >= ur {
+            ur
+        } else {
+            xi
+        };
+        u.write(0, 0, value);
+    }
+}
+
+/// Fundamental diagram of a traffic flow model, relating the density of vehicles to their flow
+///
+/// The flow is assumed to be concave.
+pub trait FundamentalDiagram<F: SimpleFloat> {
+    /// Flow `q(ρ) = ρ V(ρ)` at density `ρ`
+    fn flow(&self, rho: F) -> F;
+    /// Characteristic speed `q'(ρ)`
+    fn speed(&self, rho: F) -> F;
+    /// Density at which the characteristic speed is `c`, the inverse of `q'`
+    fn density_at_speed(&self, c: F) -> F;
+}
+
+/// Greenshields' fundamental diagram `V(ρ) = v_max (1 - ρ/ρ_max)`
+#[derive(Debug, Clone, Copy)]
+pub struct Greenshields<F> {
+    pub v_max: F,
+    pub rho_max: F,
+}
+
+impl<F: SimpleFloat> Default for Greenshields<F> {
+    /// Normalized diagram, `v_max = ρ_max = 1`
+    fn default() -> Self {
+        Self {
+            v_max: F::one(),
+            rho_max: F::one(),
+        }
+    }
+}
+
+impl<F: SimpleFloat> FundamentalDiagram<F> for Greenshields<F> {
+    fn flow(&self, rho: F) -> F {
+        self.v_max.mul(rho).mul(F::one().sub(rho.div(self.rho_max)))
+    }
+
+    fn speed(&self, rho: F) -> F {
+        let two = F::from_f64(2.);
+        self.v_max.mul(F::one().sub(two.mul(rho).div(self.rho_max)))
+    }
+
+    fn density_at_speed(&self, c: F) -> F {
+        let half = F::from_f64(0.5);
+        self.rho_max.mul(F::one().sub(c.div(self.v_max))).mul(half)
+    }
+}
+
+/// Lighthill-Whitham-Richards traffic flow model `ρ_t + q(ρ)_x = 0`, with the fundamental diagram
+/// `D`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Lwr<F, D = Greenshields<F>> {
+    diagram: D,
+    _marker: PhantomData<F>,
+}
+
+impl<F: SimpleFloat, D: FundamentalDiagram<F>> Lwr<F, D> {
+    pub fn new(diagram: D) -> Self {
+        Self {
+            diagram,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn diagram(&self) -> &D {
+        &self.diagram
+    }
+}
+
+impl<F: SimpleFloat, D: FundamentalDiagram<F>> ConservationLaw<F> for Lwr<F, D> {
+    #[inline]
+    fn system_size(&self) -> usize {
+        1
+    }
+
+    fn flux_function(&self, u: MatRef<F>, mut v: MatMut<F>) {
+        v.write(0, 0, self.diagram.flow(u.read(0, 0)));
+    }
+
+    fn jacobian(&self, u: MatRef<F>, mut jac: MatMut<F>) {
+        jac.write(0, 0, self.diagram.speed(u.read(0, 0)));
+    }
+
+    fn eigenvalues(&self, u: MatRef<F>, mut values: MatMut<F>) {
+        values.write(0, 0, self.diagram.speed(u.read(0, 0)));
+    }
+
+    fn eigen_decomposition(&self, u: MatRef<F>) -> Option<EigenDecomposition<F>> {
+        Some(scalar_decomposition(self.diagram.speed(u.read(0, 0))))
+    }
+
+    fn max_wave_speed(&self, u: MatRef<F>) -> F {
+        linalg::abs(self.diagram.speed(u.read(0, 0)))
+    }
+}
+
+impl<F: SimpleFloat, D: FundamentalDiagram<F>> ExactRiemannSolution<F> for Lwr<F, D> {
+    fn riemann_sample(&self, left: MatRef<F>, right: MatRef<F>, xi: F, mut u: MatMut<F>) {
+        let (rl, rr) = (left.read(0, 0), right.read(0, 0));
+        let q = |rho| self.diagram.flow(rho);
+
+        let value = if rl == rr {
+            rl
+        } else if rl < rr {
+            // the flow being concave, denser traffic downstream makes a shock
+            let speed = q(rl).sub(q(rr)).div(rl.sub(rr));
+            if xi < speed {
+                rl
+            } else {
+                rr
+            }
+        } else if xi <= self.diagram.speed(rl) {
+            rl
+        } else if xi >= self.diagram.speed(rr) {
+            rr
+        } else {
+            self.diagram.density_at_speed(xi)
+        };
+        u.write(0, 0, value);
+    }
+}
+
+/// Buckley-Leverett equation `u_t + f(u)_x = 0` of two-phase flow in porous media, with the
+/// non-convex fractional flow `f(u) = u² / (u² + M (1 - u)²)` of mobility ratio `M`
+#[derive(Debug, Clone, Copy)]
+pub struct BuckleyLeverett<F> {
+    mobility_ratio: F,
+}
+
+impl<F: SimpleFloat> Default for BuckleyLeverett<F> {
+    /// `M = 1/2`
+    fn default() -> Self {
+        Self::new(F::from_f64(0.5))
+    }
+}
+
+impl<F: SimpleFloat> BuckleyLeverett<F> {
+    pub fn new(mobility_ratio: F) -> Self {
+        Self { mobility_ratio }
+    }
+
+    /// Fractional flow `f(u)`
+    pub fn flux(&self, u: F) -> F {
+        let v = F::one().sub(u);
+        u.mul(u)
+            .div(u.mul(u).add(self.mobility_ratio.mul(v).mul(v)))
+    }
+
+    /// Characteristic speed `f'(u) = 2Mu(1 - u) / (u² + M (1 - u)²)²`
+    pub fn speed(&self, u: F) -> F {
+        let v = F::one().sub(u);
+        let d = u.mul(u).add(self.mobility_ratio.mul(v).mul(v));
+        F::from_f64(2.)
+            .mul(self.mobility_ratio)
+            .mul(u)
+            .mul(v)
+            .div(d.mul(d))
+    }
+
+    /// Inflection point of the fractional flow, where the characteristic speed peaks: `f` is
+    /// convex below it and concave above it on `[0, 1]`
+    ///
+    /// Located by bisection on the sign of `f''`, approximated by central differences of `f'`.
+    pub fn inflection(&self) -> F {
+        let half = F::from_f64(0.5);
+        let (mut a, mut b) = (F::zero(), F::one());
+        for _ in 0..riemann::SONIC_POINT_BISECTIONS {
+            let m = a.add(b).mul(half);
+            let h = linalg::difference_step(m);
+            if self.speed(m.add(h)) > self.speed(m.sub(h)) {
+                a = m;
+            } else {
+                b = m;
+            }
+        }
+        a.add(b).mul(half)
+    }
+}
+
+impl<F: SimpleFloat> ConservationLaw<F> for BuckleyLeverett<F> {
+    #[inline]
+    fn system_size(&self) -> usize {
+        1
+    }
+
+    fn flux_function(&self, u: MatRef<F>, mut v: MatMut<F>) {
+        v.write(0, 0, self.flux(u.read(0, 0)));
+    }
+
+    fn jacobian(&self, u: MatRef<F>, mut jac: MatMut<F>) {
+        jac.write(0, 0, self.speed(u.read(0, 0)));
+    }
+
+    fn eigenvalues(&self, u: MatRef<F>, mut values: MatMut<F>) {
+        values.write(0, 0, self.speed(u.read(0, 0)));
+    }
+
+    fn eigen_decomposition(&self, u: MatRef<F>) -> Option<EigenDecomposition<F>> {
+        Some(scalar_decomposition(self.speed(u.read(0, 0))))
+    }
+
+    fn max_wave_speed(&self, u: MatRef<F>) -> F {
+        linalg::abs(self.speed(u.read(0, 0)))
+    }
+}
+
+impl<F: SimpleFloat> ExactRiemannSolution<F> for BuckleyLeverett<F> {
+    fn riemann_sample(&self, left: MatRef<F>, right: MatRef<F>, xi: F, mut u: MatMut<F>) {
+        let value = riemann::envelope_sample(
+            &|u| self.flux(u),
+            &|u| self.speed(u),
+            self.inflection(),
+            left.read(0, 0),
+            right.read(0, 0),
+            xi,
+        );
+        u.write(0, 0, value);
+    }
+}
+
+/// p-system `v_t - w_x = 0`, `w_t + p(v)_x = 0` of isentropic gas dynamics in Lagrangian
+/// coordinates, with specific volume `v`, velocity `w` and the pressure law `p`, decreasing
+///
+/// The derivative of the pressure law is approximated by central differences.
+///
+/// There is no [`ExactRiemannSolution`] for it: with an arbitrary pressure law, the rarefaction
+/// curves `w ∓ ∫ √(-p'(v)) dv` have no closed form, and both fields are genuinely nonlinear only
+/// for a convex `p`, which a closure cannot guarantee. [`Isothermal`] solves the isothermal case
+/// `p = c²/v` exactly, in Eulerian coordinates.
+pub struct PSystem<F, P> {
+    pressure: P,
+    _marker: PhantomData<F>,
+}
+
+impl<F: SimpleFloat, P: Fn(F) -> F> PSystem<F, P> {
+    pub fn new(pressure: P) -> Self {
+        Self {
+            pressure,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Pressure `p(v)`
+    pub fn pressure(&self, v: F) -> F {
+        (self.pressure)(v)
+    }
+
+    /// Lagrangian sound speed `√(-p'(v))`
+    pub fn sound_speed(&self, v: F) -> F {
+        let h = linalg::difference_step(v);
+        let dp = self
+            .pressure(v.add(h))
+            .sub(self.pressure(v.sub(h)))
+            .div(h.add(h));
+        dp.neg().sqrt()
+    }
+}
+
+impl<F: SimpleFloat, P: Fn(F) -> F> ConservationLaw<F> for PSystem<F, P> {
+    #[inline]
+    fn system_size(&self) -> usize {
+        2
+    }
+
+    fn flux_function(&self, u: MatRef<F>, mut v: MatMut<F>) {
+        v.write(0, 0, u.read(1, 0).neg());
+        v.write(1, 0, self.pressure(u.read(0, 0)));
+    }
+
+    fn jacobian(&self, u: MatRef<F>, mut jac: MatMut<F>) {
+        let c = self.sound_speed(u.read(0, 0));
+        jac.write(0, 0, F::zero());
+        jac.write(0, 1, F::one().neg());
+        jac.write(1, 0, c.mul(c).neg());
+        jac.write(1, 1, F::zero());
+    }
+
+    fn eigenvalues(&self, u: MatRef<F>, mut values: MatMut<F>) {
+        let c = self.sound_speed(u.read(0, 0));
+        values.write(0, 0, c.neg());
+        values.write(1, 0, c);
+    }
+
+    fn eigen_decomposition(&self, u: MatRef<F>) -> Option<EigenDecomposition<F>> {
+        let c = self.sound_speed(u.read(0, 0));
+        let half = F::from_f64(0.5);
+        Some(EigenDecomposition {
+            values: Mat::from_fn(2, 1, |i, _| if i == 0 { c.neg() } else { c }),
+            right: Mat::from_fn(2, 2, |i, j| match (i, j) {
+                (0, _) => F::one(),
+                (1, 0) => c,
+                _ => c.neg(),
+            }),
+            left: Mat::from_fn(2, 2, |i, j| match (i, j) {
+                (_, 0) => half,
+                (0, 1) => half.div(c),
+                _ => half.div(c).neg(),
+            }),
+        })
+    }
+
+    fn max_wave_speed(&self, u: MatRef<F>) -> F {
+        self.sound_speed(u.read(0, 0))
+    }
+}
+
+/// Isothermal gas dynamics, Euler equations with the pressure law `p = c²ρ`, with conserved
+/// variables `(ρ, ρu)`
+#[derive(Debug, Clone, Copy)]
+pub struct Isothermal<F> {
+    c: F,
+}
+
+impl<F: SimpleFloat> Default for Isothermal<F> {
+    /// Unit sound speed
+    fn default() -> Self {
+        Self::new(F::one())
+    }
+}
+
+impl<F: SimpleFloat> Isothermal<F> {
+    pub fn new(sound_speed: F) -> Self {
+        Self { c: sound_speed }
+    }
+
+    pub fn sound_speed(&self) -> F {
+        self.c
+    }
+}
+
+impl<F: SimpleFloat> ConservationLaw<F> for Isothermal<F> {
+    #[inline]
+    fn system_size(&self) -> usize {
+        2
+    }
+
+    fn flux_function(&self, u: MatRef<F>, mut v: MatMut<F>) {
+        let (rho, m) = (u.read(0, 0), u.read(1, 0));
+        v.write(0, 0, m);
+        v.write(1, 0, m.mul(m).div(rho).add(self.c.mul(self.c).mul(rho)));
+    }
+
+    fn jacobian(&self, u: MatRef<F>, mut jac: MatMut<F>) {
+        let velocity = u.read(1, 0).div(u.read(0, 0));
+        jac.write(0, 0, F::zero());
+        jac.write(0, 1, F::one());
+        jac.write(1, 0, self.c.mul(self.c).sub(velocity.mul(velocity)));
+        jac.write(1, 1, velocity.add(velocity));
+    }
+
+    fn eigenvalues(&self, u: MatRef<F>, mut values: MatMut<F>) {
+        let velocity = u.read(1, 0).div(u.read(0, 0));
+        values.write(0, 0, velocity.sub(self.c));
+        values.write(1, 0, velocity.add(self.c));
+    }
+
+    fn eigen_decomposition(&self, u: MatRef<F>) -> Option<EigenDecomposition<F>> {
+        let velocity = u.read(1, 0).div(u.read(0, 0));
+        let (slow, fast) = (velocity.sub(self.c), velocity.add(self.c));
+        let scale = F::one().div(self.c.add(self.c));
+        Some(EigenDecomposition {
+            values: Mat::from_fn(2, 1, |i, _| if i == 0 { slow } else { fast }),
+            right: Mat::from_fn(2, 2, |i, j| match (i, j) {
+                (0, _) => F::one(),
+                (1, 0) => slow,
+                _ => fast,
+            }),
+            left: Mat::from_fn(2, 2, |i, j| match (i, j) {
+                (0, 0) => fast.mul(scale),
+                (0, 1) => scale.neg(),
+                (1, 0) => slow.mul(scale).neg(),
+                _ => scale,
+            }),
+        })
+    }
+
+    fn max_wave_speed(&self, u: MatRef<F>) -> F {
+        linalg::abs(u.read(1, 0).div(u.read(0, 0))).add(self.c)
+    }
+}
+
+const ISOTHERMAL_NEWTON_ITERATIONS: usize = 50;
+
+impl<F: SimpleFloat> ExactRiemannSolution<F> for Isothermal<F> {
+    fn riemann_sample(&self, left: MatRef<F>, right: MatRef<F>, xi: F, mut u: MatMut<F>) {
+        let c = self.c;
+        let state = |u: MatRef<F>| {
+            let rho = u.read(0, 0);
+            if rho > F::zero() {
+                (rho, u.read(1, 0).div(rho))
+            } else {
+                (F::zero(), F::zero())
+            }
+        };
+        let ((rl, ul), (rr, ur)) = (state(left), state(right));
+
+        // vacuum on a side: the rarefaction from the other side never reaches it, its density
+        // decaying exponentially
+        let (r, v) = if rl <= F::zero() && rr <= F::zero() {
+            (F::zero(), F::zero())
+        } else if rr <= F::zero() {
+            if xi < ul.sub(c) {
+                (rl, ul)
+            } else {
+                let decay = linalg::exp(ul.sub(xi).div(c).sub(F::one()));
+                (rl.mul(decay), xi.add(c))
+            }
+        } else if rl <= F::zero() {
+            if xi > ur.add(c) {
+                (rr, ur)
+            } else {
+                let decay = linalg::exp(xi.sub(ur).div(c).sub(F::one()));
+                (rr.mul(decay), xi.sub(c))
+            }
+        } else {
+            Self::wet_sample(c, (rl, ul), (rr, ur), xi)
+        };
+
+        u.write(0, 0, r);
+        u.write(1, 0, r.mul(v));
+    }
+}
+
+impl<F: SimpleFloat> Isothermal<F> {
+    /// Density and velocity at `ξ` of the Riemann problem between two states of positive density
+    fn wet_sample(c: F, (rl, ul): (F, F), (rr, ur): (F, F), xi: F) -> (F, F) {
+        let max = |a: F, b: F| if a > b { a } else { b };
+
+        // velocity jump across a wave from the density `rk` to `rho`, and its derivative
+        let jump = |rho: F, rk: F| {
+            if rho > rk {
+                let s = rho.mul(rk).sqrt();
+                (
+                    c.mul(rho.sub(rk)).div(s),
+                    c.mul(rk).mul(rho.add(rk)).div(s.add(s).mul(rho).mul(rk)),
+                )
+            } else {
+                (c.mul(linalg::ln(rho.div(rk))), c.div(rho))
+            }
+        };
+
+        // star density by Newton's method, from the exact two-rarefaction solution
+        let tolerance = F::from_f64(64.).mul(linalg::epsilon());
+        let mut rho = rl.mul(rr).sqrt().mul(linalg::exp(ul.sub(ur).div(c.add(c))));
+        for _ in 0..ISOTHERMAL_NEWTON_ITERATIONS {
+            let ((fl, dfl), (fr, dfr)) = (jump(rho, rl), jump(rho, rr));
+            let step = fl.add(fr).add(ur).sub(ul).div(dfl.add(dfr));
+            let next = max(rho.sub(step), rho.mul(F::from_f64(1e-3)));
+            let converged = linalg::abs(next.sub(rho)) <= tolerance.mul(rho);
+            rho = next;
+            if converged {
+                break;
+            }
+        }
+        let velocity = ul.sub(jump(rho, rl).0);
+
+        if xi < velocity {
+            if rho > rl {
+                let shock = ul.sub(c.mul(rho.div(rl).sqrt()));
+                if xi < shock {
+                    (rl, ul)
+                } else {
+                    (rho, velocity)
+                }
+            } else if xi < ul.sub(c) {
+                (rl, ul)
+            } else if xi > velocity.sub(c) {
+                (rho, velocity)
+            } else {
+                let v = xi.add(c);
+                (rl.mul(linalg::exp(ul.sub(v).div(c))), v)
+            }
+        } else if rho > rr {
+            let shock = ur.add(c.mul(rho.div(rr).sqrt()));
+            if xi > shock {
+                (rr, ur)
+            } else {
+                (rho, velocity)
+            }
+        } else if xi > ur.add(c) {
+            (rr, ur)
+        } else if xi < velocity.add(c) {
+            (rho, velocity)
+        } else {
+            let v = xi.sub(c);
+            (rr.mul(linalg::exp(v.sub(ur).div(c))), v)
+        }
+    }
+}
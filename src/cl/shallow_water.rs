@@ -0,0 +1,343 @@
+use faer_core::{Mat, MatMut, MatRef};
+
+use crate::{
+    linalg,
+    riemann::{ExactRiemannSolution, Wave},
+    ConservationLaw, EigenDecomposition, SimpleFloat, SourceTerm,
+};
+
+/// Depth below which a cell is considered dry by default
+const DRY_TOLERANCE: f64 = 1e-8;
+
+/// One-dimensional shallow-water equations with gravity `g`, with conserved variables `(h, hu)`
+///
+/// Cells shallower than the dry tolerance are considered dry: their velocity is zero, so that
+/// fluxes and wave speeds stay bounded at wet/dry fronts.
+#[derive(Debug, Clone, Copy)]
+pub struct ShallowWater<F> {
+    g: F,
+    dry_tolerance: F,
+}
+
+impl<F: SimpleFloat> Default for ShallowWater<F> {
+    /// Earth's gravity (`g = 9.81`)
+    fn default() -> Self {
+        Self::new(F::from_f64(9.81))
+    }
+}
+
+impl<F: SimpleFloat> ShallowWater<F> {
+    pub fn new(gravity: F) -> Self {
+        Self {
+            g: gravity,
+            dry_tolerance: F::from_f64(DRY_TOLERANCE),
+        }
+    }
+
+    pub fn with_dry_tolerance(self, dry_tolerance: F) -> Self {
+        Self {
+            dry_tolerance,
+            ..self
+        }
+    }
+
+    pub fn gravity(&self) -> F {
+        self.g
+    }
+
+    pub fn dry_tolerance(&self) -> F {
+        self.dry_tolerance
+    }
+
+    pub fn is_dry(&self, h: F) -> bool {
+        h <= self.dry_tolerance
+    }
+
+    /// Velocity `hu/h` of the state `u`, zero in dry cells
+    pub fn velocity(&self, u: MatRef<F>) -> F {
+        let h = u.read(0, 0);
+        if self.is_dry(h) {
+            F::zero()
+        } else {
+            u.read(1, 0).div(h)
+        }
+    }
+
+    /// Celerity `c = √(gh)` of the state `u`
+    pub fn celerity(&self, u: MatRef<F>) -> F {
+        let h = u.read(0, 0);
+        if h > F::zero() {
+            self.g.mul(h).sqrt()
+        } else {
+            F::zero()
+        }
+    }
+
+    /// Source term `(0, -g h b'(x))` of the bathymetry `b`, to be added to the problem with
+    /// [`Problem::with_source`](crate::Problem::with_source)
+    ///
+    /// Being discretised independently of the fluxes, this source does not preserve the lake at
+    /// rest exactly, see [`HydrostaticReconstruction`](crate::methods::HydrostaticReconstruction)
+    /// for a well-balanced alternative.
+    pub fn bathymetry_source<'a>(&self, bathymetry: impl Fn(F) -> F + 'a) -> impl SourceTerm<F> + 'a
+    where
+        F: 'a,
+    {
+        let g = self.g;
+        move |u: MatRef<F>, x: F, _t: F, mut s: MatMut<F>| {
+            let dx = linalg::difference_step(x);
+            let slope = bathymetry(x.add(dx))
+                .sub(bathymetry(x.sub(dx)))
+                .div(dx.add(dx));
+            s.write(0, 0, F::zero());
+            s.write(1, 0, g.mul(u.read(0, 0)).mul(slope).neg());
+        }
+    }
+}
+
+impl<F: SimpleFloat> ConservationLaw<F> for ShallowWater<F> {
+    #[inline]
+    fn system_size(&self) -> usize {
+        2
+    }
+
+    fn flux_function(&self, u: MatRef<F>, mut v: MatMut<F>) {
+        let (h, m) = (u.read(0, 0), u.read(1, 0));
+        let pressure = self.g.mul(h).mul(h).mul(F::from_f64(0.5));
+        v.write(0, 0, m);
+        v.write(1, 0, m.mul(self.velocity(u)).add(pressure));
+    }
+
+    fn jacobian(&self, u: MatRef<F>, mut jac: MatMut<F>) {
+        let velocity = self.velocity(u);
+        jac.write(0, 0, F::zero());
+        jac.write(0, 1, F::one());
+        jac.write(1, 0, self.g.mul(u.read(0, 0)).sub(velocity.mul(velocity)));
+        jac.write(1, 1, velocity.add(velocity));
+    }
+
+    fn eigenvalues(&self, u: MatRef<F>, mut values: MatMut<F>) {
+        let (velocity, c) = (self.velocity(u), self.celerity(u));
+        values.write(0, 0, velocity.sub(c));
+        values.write(1, 0, velocity.add(c));
+    }
+
+    /// Undefined in dry cells, where both waves coincide
+    fn eigen_decomposition(&self, u: MatRef<F>) -> Option<EigenDecomposition<F>> {
+        if self.is_dry(u.read(0, 0)) {
+            return None;
+        }
+
+        let (velocity, c) = (self.velocity(u), self.celerity(u));
+        let (slow, fast) = (velocity.sub(c), velocity.add(c));
+        let scale = F::one().div(c.add(c));
+        Some(EigenDecomposition {
+            values: Mat::from_fn(2, 1, |i, _| if i == 0 { slow } else { fast }),
+            right: Mat::from_fn(2, 2, |i, j| match (i, j) {
+                (0, _) => F::one(),
+                (1, 0) => slow,
+                _ => fast,
+            }),
+            left: Mat::from_fn(2, 2, |i, j| match (i, j) {
+                (0, 0) => fast.mul(scale),
+                (0, 1) => scale.neg(),
+                (1, 0) => slow.mul(scale).neg(),
+                _ => scale,
+            }),
+        })
+    }
+
+    fn max_wave_speed(&self, u: MatRef<F>) -> F {
+        linalg::abs(self.velocity(u)).add(self.celerity(u))
+    }
+}
+
+const RIEMANN_NEWTON_ITERATIONS: usize = 50;
+
+/// Depth and velocity of a [`ShallowWater`] state
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Flow<F> {
+    pub depth: F,
+    pub velocity: F,
+}
+
+/// Exact self-similar solution of a Riemann problem of the [`ShallowWater`] equations over a flat
+/// bottom, wet or dry, after Toro
+#[derive(Debug, Clone, Copy)]
+pub struct ShallowWaterRiemannSolution<F> {
+    pub left: Flow<F>,
+    pub right: Flow<F>,
+    pub left_wave: Wave<F>,
+    pub right_wave: Wave<F>,
+    /// `None` when the waves are separated by a dry region
+    pub star: Option<Flow<F>>,
+    g: F,
+}
+
+impl<F: SimpleFloat> ShallowWaterRiemannSolution<F> {
+    /// Solves the Riemann problem, the star depth being found by Newton's method
+    fn new(shallow_water: &ShallowWater<F>, left: Flow<F>, right: Flow<F>) -> Self {
+        let c = |x: f64| F::from_f64(x);
+        let max = |a: F, b: F| if a > b { a } else { b };
+        let g = shallow_water.g;
+        let state = |w: &Flow<F>| {
+            if shallow_water.is_dry(w.depth) {
+                (F::zero(), F::zero())
+            } else {
+                (w.depth, w.velocity)
+            }
+        };
+        let ((hl, ul), (hr, ur)) = (state(&left), state(&right));
+        let (cl, cr) = (g.mul(hl).sqrt(), g.mul(hr).sqrt());
+        let fan = |head: F, tail: F| Wave::Rarefaction { head, tail };
+        let solution = |left_wave, right_wave, star| Self {
+            left,
+            right,
+            left_wave,
+            right_wave,
+            star,
+            g,
+        };
+
+        // dry bed, initially on a side or created between two rarefactions
+        let (escape_l, escape_r) = (ul.add(c(2.).mul(cl)), ur.sub(c(2.).mul(cr)));
+        if hl <= F::zero() || hr <= F::zero() || escape_l <= escape_r {
+            let left_wave = if hl > F::zero() {
+                fan(ul.sub(cl), escape_l)
+            } else {
+                fan(escape_r, escape_r)
+            };
+            let right_wave = if hr > F::zero() {
+                fan(ur.add(cr), escape_r)
+            } else {
+                fan(escape_l, escape_l)
+            };
+            return solution(left_wave, right_wave, None);
+        }
+
+        // velocity jump across a wave from the depth hk to h, and its derivative
+        let jump = |h: F, hk: F| {
+            if h > hk {
+                let s = c(0.5).mul(g).mul(h.add(hk)).div(h.mul(hk)).sqrt();
+                let dh = h.sub(hk);
+                let ds = g.mul(dh).div(c(4.).mul(s).mul(h).mul(h));
+                (dh.mul(s), s.sub(ds))
+            } else {
+                (
+                    c(2.).mul(g.mul(h).sqrt().sub(g.mul(hk).sqrt())),
+                    g.div(h).sqrt(),
+                )
+            }
+        };
+
+        // star depth by Newton's method, from the exact two-rarefaction solution
+        let tolerance = c(64.).mul(linalg::epsilon());
+        let quarter = c(0.25).mul(escape_l.sub(escape_r));
+        let mut h = quarter.mul(quarter).div(g);
+        for _ in 0..RIEMANN_NEWTON_ITERATIONS {
+            let ((fl, dfl), (fr, dfr)) = (jump(h, hl), jump(h, hr));
+            let step = fl.add(fr).add(ur).sub(ul).div(dfl.add(dfr));
+            let next = max(h.sub(step), h.mul(c(1e-3)));
+            let converged = linalg::abs(next.sub(h)) <= tolerance.mul(h);
+            h = next;
+            if converged {
+                break;
+            }
+        }
+        let velocity = ul.add(ur).add(jump(h, hr).0).sub(jump(h, hl).0).mul(c(0.5));
+        let celerity = g.mul(h).sqrt();
+
+        let shock = |hk: F| c(0.5).mul(h.add(hk)).mul(h).div(hk.mul(hk)).sqrt();
+        let left_wave = if h > hl {
+            Wave::Shock {
+                speed: ul.sub(cl.mul(shock(hl))),
+            }
+        } else {
+            fan(ul.sub(cl), velocity.sub(celerity))
+        };
+        let right_wave = if h > hr {
+            Wave::Shock {
+                speed: ur.add(cr.mul(shock(hr))),
+            }
+        } else {
+            fan(ur.add(cr), velocity.add(celerity))
+        };
+
+        let star = Flow { depth: h, velocity };
+        solution(left_wave, right_wave, Some(star))
+    }
+
+    /// Depth and velocity at `ξ = x/t`
+    pub fn sample(&self, xi: F) -> Flow<F> {
+        let c = |x: f64| F::from_f64(x);
+        let g = self.g;
+
+        // inside the fan bordering the state w, with `sign` 1 on the left and -1 on the right
+        let fan = |w: &Flow<F>, sign: F| {
+            let (h, u) = (w.depth, w.velocity);
+            let c_w = g.mul(h).sqrt();
+            let celerity = sign.mul(u.sub(xi)).add(c(2.).mul(c_w)).div(c(3.));
+            Flow {
+                depth: celerity.mul(celerity).div(g),
+                velocity: u
+                    .add(sign.mul(c(2.)).mul(c_w))
+                    .add(c(2.).mul(xi))
+                    .div(c(3.)),
+            }
+        };
+        let side = |w: &Flow<F>, wave: Wave<F>, sign: F, star: Flow<F>| {
+            if sign.mul(xi) < sign.mul(wave.outer_speed()) {
+                *w
+            } else if wave.is_shock() || sign.mul(xi) >= sign.mul(wave.inner_speed()) {
+                star
+            } else {
+                fan(w, sign)
+            }
+        };
+
+        let (left, right) = (F::one(), F::one().neg());
+        let (star, inner) = match self.star {
+            Some(star) => (star, star.velocity),
+            None => {
+                let dry = Flow {
+                    depth: F::zero(),
+                    velocity: F::zero(),
+                };
+                (dry, self.left_wave.inner_speed())
+            }
+        };
+        if xi < inner {
+            side(&self.left, self.left_wave, left, star)
+        } else {
+            side(&self.right, self.right_wave, right, star)
+        }
+    }
+}
+
+impl<F: SimpleFloat> ShallowWater<F> {
+    /// Depth and velocity of the state `u`
+    pub fn flow(&self, u: MatRef<F>) -> Flow<F> {
+        Flow {
+            depth: u.read(0, 0),
+            velocity: self.velocity(u),
+        }
+    }
+
+    /// Writes into `u` the conserved variables of the flow `w`
+    pub fn conserved(&self, w: &Flow<F>, mut u: MatMut<F>) {
+        u.write(0, 0, w.depth);
+        u.write(1, 0, w.depth.mul(w.velocity));
+    }
+
+    /// Exact solution of the Riemann problem between the flows `left` and `right`
+    pub fn riemann(&self, left: &Flow<F>, right: &Flow<F>) -> ShallowWaterRiemannSolution<F> {
+        ShallowWaterRiemannSolution::new(self, *left, *right)
+    }
+}
+
+impl<F: SimpleFloat> ExactRiemannSolution<F> for ShallowWater<F> {
+    fn riemann_sample(&self, left: MatRef<F>, right: MatRef<F>, xi: F, u: MatMut<F>) {
+        let solution = self.riemann(&self.flow(left), &self.flow(right));
+        self.conserved(&solution.sample(xi), u)
+    }
+}
@@ -1,16 +1,44 @@
 use std::{io::Write, rc::Rc};
 
 use bytemuck::bytes_of;
-use faer_core::{Mat, MatRef};
+use faer_core::{zipped, Mat, MatMut, MatRef};
 use reborrow::*;
 use thiserror::Error;
 
-use crate::{mesh::Mesh, method::Method, sim::Simulation, Ctx, Problem, Resolution, SimpleFloat};
+use crate::{
+    linalg,
+    mesh::Mesh,
+    method::Method,
+    sim::{Simulation, Splitting, TimeStepping},
+    ConservationLaw, Ctx, Problem, Resolution, SimpleFloat, SourceTerm,
+};
 
 #[derive(Error, Debug)]
 pub enum SimError {
     #[error("output error")]
     Io(#[from] std::io::Error),
+    #[error("CFL number {cfl} exceeds the stability limit {limit} of the method at step {iter}")]
+    CflViolated { iter: usize, cfl: f64, limit: f64 },
+    #[error("solution diverged at step {iter} (t = {time}) in cell {cell}, component {component}")]
+    Diverged {
+        iter: usize,
+        time: f64,
+        cell: usize,
+        component: usize,
+    },
+    #[error("wave speed {speed} is not finite at step {iter} (t = {time})")]
+    InvalidWaveSpeed { iter: usize, time: f64, speed: f64 },
+}
+
+/// What to do when the CFL number exceeds the stability limit of the method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CflPolicy {
+    Ignore,
+    /// Emit a warning through `tracing`
+    #[default]
+    Warn,
+    /// Abort with [`SimError::CflViolated`]
+    Abort,
 }
 
 pub struct ObsCtx<'pb, 'ctx, F: SimpleFloat> {
@@ -18,12 +46,14 @@ pub struct ObsCtx<'pb, 'ctx, F: SimpleFloat> {
     problem: &'ctx Problem<'pb, F>,
     mesh: &'ctx Mesh<F>,
     method: &'ctx dyn Method<F>,
+    time_stepping: TimeStepping<F>,
     time_sampling: usize,
     space_sampling: usize,
 
     // Iteration info
     iter: usize,
     time: F,
+    time_step: F,
     solution: MatRef<'ctx, F>, // current solution *without* ghost cells
 }
 
@@ -40,6 +70,10 @@ impl<'pb, 'ctx, F: SimpleFloat> ObsCtx<'pb, 'ctx, F> {
         self.method
     }
 
+    pub fn time_stepping(&self) -> TimeStepping<F> {
+        self.time_stepping
+    }
+
     pub fn iter(&self) -> usize {
         self.iter
     }
@@ -48,6 +82,11 @@ impl<'pb, 'ctx, F: SimpleFloat> ObsCtx<'pb, 'ctx, F> {
         self.time
     }
 
+    /// Time step that led to the current solution (the upcoming one at startup)
+    pub fn time_step(&self) -> F {
+        self.time_step
+    }
+
     pub fn solution(&self) -> MatRef<'_, F> {
         self.solution
     }
@@ -57,6 +96,184 @@ impl<'pb, 'ctx, F: SimpleFloat> ObsCtx<'pb, 'ctx, F> {
     }
 }
 
+/// Returns the time step to take after step `n` at time `t`, along with the time reached, or
+/// `None` once the end of the time domain is reached
+///
+/// Adaptive steps never exceed the nominal time step of the mesh, which bounds them when the
+/// solution has no wave speed, and fail on a wave speed which is not finite.
+fn next_time_step<F: SimpleFloat>(
+    time_stepping: TimeStepping<F>,
+    cl: &dyn ConservationLaw<F>,
+    mesh: &Mesh<F>,
+    n: usize,
+    t: F,
+    u: MatRef<F>,
+) -> Result<Option<(F, F)>, SimError> {
+    match time_stepping {
+        TimeStepping::Fixed => Ok((n < mesh.time.steps).then(|| {
+            let next = mesh
+                .time
+                .lower
+                .add(mesh.time.delta.mul(F::from_f64((n + 1) as f64)));
+            (mesh.time.delta, next)
+        })),
+        TimeStepping::Cfl(cfl) => {
+            let remaining = mesh.time.upper.sub(t);
+            if remaining <= F::zero() {
+                return Ok(None);
+            }
+
+            let speed = cl.bulk_max_wave_speed(u);
+            if !speed.is_finite() {
+                return Err(SimError::InvalidWaveSpeed {
+                    iter: n,
+                    time: reported(t),
+                    speed: reported(speed),
+                });
+            }
+            let dt = if speed > F::zero() {
+                let dt = cfl.mul(mesh.space.delta).div(speed);
+                if dt < mesh.time.delta {
+                    dt
+                } else {
+                    mesh.time.delta
+                }
+            } else {
+                mesh.time.delta
+            };
+            // a sliver left by the rounding of the previous steps is merged into the last one
+            let sliver = dt.mul(linalg::epsilon::<F>().sqrt());
+            Ok(Some(if remaining.sub(dt) > sliver {
+                (dt, t.add(dt))
+            } else {
+                (remaining, mesh.time.upper)
+            }))
+        }
+    }
+}
+
+/// Whether step `n`, which reached time `t`, is the last one of the simulation
+fn is_last_step<F: SimpleFloat>(
+    time_stepping: TimeStepping<F>,
+    mesh: &Mesh<F>,
+    n: usize,
+    t: F,
+) -> bool {
+    match time_stepping {
+        TimeStepping::Fixed => n >= mesh.time.steps,
+        TimeStepping::Cfl(_) => t >= mesh.time.upper,
+    }
+}
+
+/// Value of `x` in a warning or a [`SimError`], read from its debug representation since
+/// `RealField` has no conversion to `f64`
+fn reported<F: SimpleFloat>(x: F) -> f64 {
+    format!("{x:?}").parse().unwrap_or(f64::NAN)
+}
+
+/// Checks the CFL number `Δt/Δx · max|f'(u)|` of step `iter` against the stability limit of
+/// `method`
+fn check_cfl<F: SimpleFloat>(
+    policy: CflPolicy,
+    method: &dyn Method<F>,
+    cl: &dyn ConservationLaw<F>,
+    mesh: &Mesh<F>,
+    iter: usize,
+    dt: F,
+    u: MatRef<F>,
+) -> Result<(), SimError> {
+    if policy == CflPolicy::Ignore {
+        return Ok(());
+    }
+
+    let cfl = dt.div(mesh.space.delta).mul(cl.bulk_max_wave_speed(u));
+    let limit = method.cfl_limit();
+    // NaN wave speeds are left to be caught elsewhere
+    if cfl <= limit || cfl.is_nan() {
+        return Ok(());
+    }
+    let (cfl, limit) = (reported(cfl), reported(limit));
+
+    match policy {
+        CflPolicy::Ignore => Ok(()),
+        CflPolicy::Warn => {
+            tracing::event!(
+                tracing::Level::WARN,
+                "CFL number {:e} exceeds the stability limit {:e} of the `{}` method at step {}",
+                cfl,
+                limit,
+                method.name(),
+                iter,
+            );
+            Ok(())
+        }
+        CflPolicy::Abort => Err(SimError::CflViolated { iter, cfl, limit }),
+    }
+}
+
+/// Returns the cell and component of the first value of `u` which is not finite or exceeds
+/// `bound` in absolute value
+fn find_divergence<F: SimpleFloat>(
+    u: MatRef<F>,
+    system_size: usize,
+    bound: Option<F>,
+) -> Option<(usize, usize)> {
+    (0..u.nrows())
+        .find(|&i| {
+            let x = u.read(i, 0);
+            !x.is_finite() || bound.is_some_and(|bound| x > bound || x.neg() > bound)
+        })
+        .map(|i| (i / system_size, i % system_size))
+}
+
+/// Adds `Δt s(u, x, t)` to `v`, cell by cell
+fn add_source<F: SimpleFloat>(
+    source: &dyn SourceTerm<F>,
+    mesh: &Mesh<F>,
+    system_size: usize,
+    t: F,
+    dt: F,
+    u: MatRef<F>,
+    v: MatMut<F>,
+) {
+    let mut s = Mat::<F>::zeros(system_size, 1);
+    for ((x, u), mut v) in mesh
+        .space
+        .iter()
+        .zip(u.into_row_chunks(system_size))
+        .zip(v.into_row_chunks(system_size))
+    {
+        source(u, x, t, s.as_mut());
+        zipped!(v.rb_mut(), s.as_ref())
+            .for_each(|mut v, s| v.write(v.read().add(dt.mul(s.read()))));
+    }
+}
+
+/// Integrates `u_t = s(u, x, t)` in place from `t` to `t + h` with Heun's method
+fn source_step<F: SimpleFloat>(
+    source: &dyn SourceTerm<F>,
+    mesh: &Mesh<F>,
+    system_size: usize,
+    t: F,
+    h: F,
+    u: MatMut<F>,
+) {
+    let half = F::from_f64(0.5);
+    let mut s = Mat::<F>::zeros(system_size, 1);
+    let mut predictor = Mat::<F>::zeros(system_size, 1);
+    for (x, mut u) in mesh.space.iter().zip(u.into_row_chunks(system_size)) {
+        source(u.rb(), x, t, s.as_mut());
+        zipped!(predictor.as_mut(), u.rb(), s.as_ref())
+            .for_each(|mut p, u, s| p.write(u.read().add(h.mul(s.read()))));
+        zipped!(u.rb_mut(), s.as_ref())
+            .for_each(|mut u, s| u.write(u.read().add(h.mul(half).mul(s.read()))));
+
+        source(predictor.as_ref(), x, t.add(h), s.as_mut());
+        zipped!(u.rb_mut(), s.as_ref())
+            .for_each(|mut u, s| u.write(u.read().add(h.mul(half).mul(s.read()))));
+    }
+}
+
 #[allow(unused_variables)]
 pub trait Observer<F: SimpleFloat> {
     fn at_startup(&mut self, ctx: ObsCtx<F>) -> Result<(), SimError> {
@@ -76,7 +293,12 @@ pub struct Driver<'pb, 'd, F: SimpleFloat, M> {
     pub(crate) sim: Simulation<'pb, F, M>,
     pub(crate) observers: Vec<Box<dyn Observer<F> + 'd>>,
     pub(crate) time_sampling: usize,
+    /// Sampling period in time units rather than steps, see [`Driver::with_time_sampling`]
+    pub(crate) sampling_interval: Option<F>,
     pub(crate) space_sampling: usize,
+    pub(crate) cfl_policy: CflPolicy,
+    pub(crate) cfl_check_each_sample: bool,
+    pub(crate) divergence_bound: Option<F>,
 }
 
 impl<'pb, 'd, F: SimpleFloat, M: Method<F>> Driver<'pb, 'd, F, M> {
@@ -86,19 +308,40 @@ impl<'pb, 'd, F: SimpleFloat, M: Method<F>> Driver<'pb, 'd, F, M> {
             sim,
             observers: Vec::new(),
             time_sampling,
+            sampling_interval: None,
             space_sampling: 1,
+            cfl_policy: CflPolicy::default(),
+            cfl_check_each_sample: false,
+            divergence_bound: None,
         }
     }
 
+    /// A period in time units samples the step ending closest to each multiple of it, on the
+    /// actual time of the steps under [`TimeStepping::Cfl`], and sets the nominal period in steps
+    /// of [`ObsCtx::sampling_period`] from the nominal time step
+    ///
+    /// The last step is always sampled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the period is not positive.
     pub fn with_time_sampling(mut self, sampling_period: Resolution<F>) -> Self
     where
         F: Into<f64>,
     {
-        self.time_sampling = match sampling_period {
+        (self.time_sampling, self.sampling_interval) = match sampling_period {
             Resolution::Delta(sampling_period) => {
-                sampling_period.div(self.sim.mesh.time.delta).into().ceil() as usize
+                assert!(
+                    sampling_period > F::zero(),
+                    "time sampling period must be positive"
+                );
+                let steps = sampling_period.div(self.sim.mesh.time.delta).into().ceil();
+                (steps.max(1.) as usize, Some(sampling_period))
+            }
+            Resolution::Steps(sampling_period) => {
+                assert!(sampling_period > 0, "time sampling period must be positive");
+                (sampling_period, None)
             }
-            Resolution::Steps(sampling_period) => sampling_period,
         };
         self
     }
@@ -116,6 +359,23 @@ impl<'pb, 'd, F: SimpleFloat, M: Method<F>> Driver<'pb, 'd, F, M> {
         self
     }
 
+    /// The CFL condition is always checked on the initial condition, and also on the solution at
+    /// each sampled step when `each_sample` is set
+    pub fn with_cfl_check(mut self, policy: CflPolicy, each_sample: bool) -> Self {
+        self.cfl_policy = policy;
+        self.cfl_check_each_sample = each_sample;
+        self
+    }
+
+    /// Aborts with [`SimError::Diverged`] as soon as a value of the solution exceeds `bound` in
+    /// absolute value (NaN and infinite values are always detected)
+    ///
+    /// The observers are cleaned up on the diverged solution before the error is returned.
+    pub fn with_divergence_bound(mut self, bound: F) -> Self {
+        self.divergence_bound = Some(bound);
+        self
+    }
+
     pub fn with_observer(mut self, observer: impl Observer<F> + 'd) -> Self {
         self.observers.push(Box::new(observer));
         self
@@ -126,11 +386,16 @@ impl<'pb, 'd, F: SimpleFloat, M: Method<F>> Driver<'pb, 'd, F, M> {
             problem,
             mesh,
             method,
+            time_stepping,
+            splitting,
         } = &mut self.sim;
+        let (time_stepping, splitting) = (*time_stepping, *splitting);
+        let source = problem.source.clone();
 
-        let left_count = method.left_ghost_cells();
-        let center_count = mesh.space.steps + 1;
-        let right_count = method.right_ghost_cells();
+        let system_size = problem.cl.system_size();
+        let left_count = method.left_ghost_cells() * system_size;
+        let center_count = (mesh.space.steps + 1) * system_size;
+        let right_count = method.right_ghost_cells() * system_size;
 
         let mut buffer = Mat::<F>::zeros(left_count + center_count + right_count, 2);
 
@@ -144,7 +409,7 @@ impl<'pb, 'd, F: SimpleFloat, M: Method<F>> Driver<'pb, 'd, F, M> {
             for (x, u) in mesh
                 .space
                 .iter()
-                .zip(center.rb_mut().into_row_chunks(problem.cl.system_size()))
+                .zip(center.rb_mut().into_row_chunks(system_size))
             {
                 (problem.u0)(x, u)
             }
@@ -154,12 +419,15 @@ impl<'pb, 'd, F: SimpleFloat, M: Method<F>> Driver<'pb, 'd, F, M> {
                 .clone_from(center.rb());
 
             let ctx = Ctx {
-                system_size: problem.cl.system_size(),
-                left_ghost_cells: left_count,
-                right_ghost_cells: right_count,
+                system_size,
+                left_ghost_cells: method.left_ghost_cells(),
+                right_ghost_cells: method.right_ghost_cells(),
+                problem,
                 mesh,
                 n: 0,
                 t: mesh.time.lower,
+                dt: F::zero(),
+                splitting,
                 // v here because we're mutating u directly
                 u: v.rb(),
             };
@@ -169,29 +437,105 @@ impl<'pb, 'd, F: SimpleFloat, M: Method<F>> Driver<'pb, 'd, F, M> {
             // use this occasion to instantiate the method's buffer
             method.init(ctx);
 
+            let time_step = next_time_step(
+                time_stepping,
+                problem.cl.as_ref(),
+                mesh,
+                0,
+                mesh.time.lower,
+                center.rb(),
+            )?
+            .map_or(F::zero(), |(dt, _)| dt);
+
+            check_cfl(
+                self.cfl_policy,
+                method,
+                problem.cl.as_ref(),
+                mesh,
+                0,
+                time_step,
+                center.rb(),
+            )?;
+
             for o in self.observers.iter_mut() {
                 o.at_startup(ObsCtx {
                     problem,
                     mesh,
                     method,
+                    time_stepping,
                     time_sampling: self.time_sampling,
                     space_sampling: self.space_sampling,
                     iter: 0,
                     time: mesh.time.lower,
+                    time_step,
                     solution: center.as_ref(),
                 })?;
             }
         }
 
         // propagate solution
-        for (n, t) in mesh.time.iter().enumerate().skip(1) {
+        let mut n = 0;
+        let mut t = mesh.time.lower;
+        let mut time_step = F::zero();
+        // number of sampling intervals elapsed, when sampling in time units
+        let mut intervals = 0;
+        while let Some((dt, next)) = next_time_step(
+            time_stepping,
+            problem.cl.as_ref(),
+            mesh,
+            n,
+            t,
+            u.rb().subrows(left_count, center_count),
+        )? {
+            let previous = t;
+            n += 1;
+            t = next;
+            time_step = dt;
+
+            // first half step of source, after which the ghost cells of u are outdated
+            if let (Some(source), Splitting::Strang) = (&source, splitting) {
+                let [u_left, u_right] = u.rb_mut().split_at_row(left_count);
+                let [mut u_center, u_right] = u_right.split_at_row(center_count);
+                let half = dt.mul(F::from_f64(0.5));
+                source_step(
+                    source.as_ref(),
+                    mesh,
+                    system_size,
+                    previous,
+                    half,
+                    u_center.rb_mut(),
+                );
+                v.rb_mut()
+                    .subrows(left_count, center_count)
+                    .clone_from(u_center.rb());
+
+                let ctx = Ctx {
+                    system_size,
+                    left_ghost_cells: method.left_ghost_cells(),
+                    right_ghost_cells: method.right_ghost_cells(),
+                    problem,
+                    mesh,
+                    n,
+                    t: previous,
+                    dt,
+                    splitting,
+                    // v, holding a copy of the updated cells, because the ghost cells of u are
+                    // being filled
+                    u: v.rb(),
+                };
+                problem.bc.apply(ctx, u_left, u_center.rb(), u_right);
+            }
+
             let ctx = Ctx {
-                system_size: problem.cl.system_size(),
-                left_ghost_cells: left_count,
-                right_ghost_cells: right_count,
+                system_size,
+                left_ghost_cells: method.left_ghost_cells(),
+                right_ghost_cells: method.right_ghost_cells(),
+                problem,
                 mesh,
                 n,
                 t,
+                dt,
+                splitting,
                 // u here because we'll be mutating v
                 u: u.rb(),
             };
@@ -207,19 +551,100 @@ impl<'pb, 'd, F: SimpleFloat, M: Method<F>> Driver<'pb, 'd, F, M> {
                 u_center.rb(),
                 v_center.rb_mut(),
             );
+            match (&source, splitting) {
+                (Some(source), Splitting::Unsplit) if !method.integrates_source() => add_source(
+                    source.as_ref(),
+                    mesh,
+                    system_size,
+                    previous,
+                    dt,
+                    u_center.rb(),
+                    v_center.rb_mut(),
+                ),
+                (Some(source), Splitting::Strang) => {
+                    let half = dt.mul(F::from_f64(0.5));
+                    source_step(
+                        source.as_ref(),
+                        mesh,
+                        system_size,
+                        previous.add(half),
+                        half,
+                        v_center.rb_mut(),
+                    )
+                }
+                _ => {}
+            }
+
             // apply boundary condition to v
             problem.bc.apply(ctx, v_left, v_center.rb(), v_right);
 
-            if n % self.time_sampling == 0 {
+            if let Some((cell, component)) =
+                find_divergence(v_center.rb(), system_size, self.divergence_bound)
+            {
+                // observers still get to complete their output, with the diverged solution
+                for o in self.observers.iter_mut() {
+                    o.at_cleanup(ObsCtx {
+                        problem,
+                        mesh,
+                        method,
+                        time_stepping,
+                        time_sampling: self.time_sampling,
+                        space_sampling: self.space_sampling,
+                        iter: n,
+                        time: t,
+                        time_step: dt,
+                        solution: v_center.as_ref(),
+                    })?;
+                }
+                return Err(SimError::Diverged {
+                    iter: n,
+                    time: reported(t),
+                    cell,
+                    component,
+                });
+            }
+
+            let sampled = match self.sampling_interval {
+                None => n % self.time_sampling == 0,
+                Some(interval) => {
+                    // the step ending closest to the next multiple of the interval
+                    let reached = t.add(dt.mul(F::from_f64(0.5)));
+                    let elapsed = intervals;
+                    while mesh
+                        .time
+                        .lower
+                        .add(interval.mul(F::from_f64((intervals + 1) as f64)))
+                        <= reached
+                    {
+                        intervals += 1;
+                    }
+                    intervals > elapsed
+                }
+            };
+            if sampled || is_last_step(time_stepping, mesh, n, t) {
+                if self.cfl_check_each_sample {
+                    check_cfl(
+                        self.cfl_policy,
+                        method,
+                        problem.cl.as_ref(),
+                        mesh,
+                        n,
+                        dt,
+                        v_center.rb(),
+                    )?;
+                }
+
                 for o in self.observers.iter_mut() {
                     o.at_each_iteration(ObsCtx {
                         problem,
                         mesh,
                         method,
+                        time_stepping,
                         time_sampling: self.time_sampling,
                         space_sampling: self.space_sampling,
                         iter: n,
                         time: t,
+                        time_step: dt,
                         solution: v_center.as_ref(),
                     })?;
                 }
@@ -234,10 +659,12 @@ impl<'pb, 'd, F: SimpleFloat, M: Method<F>> Driver<'pb, 'd, F, M> {
                 problem,
                 mesh,
                 method,
+                time_stepping,
                 time_sampling: self.time_sampling,
                 space_sampling: self.space_sampling,
-                iter: mesh.space.steps + 1,
-                time: mesh.space.upper.add(mesh.space.delta),
+                iter: n,
+                time: t,
+                time_step,
                 solution: u.rb().subrows(left_count, center_count),
             })?;
         }
@@ -250,15 +677,24 @@ pub struct Logger;
 
 impl<F: SimpleFloat + std::fmt::LowerExp> Observer<F> for Logger {
     fn at_startup(&mut self, ctx: ObsCtx<F>) -> Result<(), SimError> {
+        let time_stepping = match ctx.time_stepping() {
+            TimeStepping::Fixed => format!(
+                "Δt={:e} ({} steps)",
+                ctx.mesh().time.delta,
+                ctx.mesh().time.steps
+            ),
+            TimeStepping::Cfl(cfl) => {
+                format!("adaptive Δt={:e} (CFL={:e})", ctx.time_step(), cfl)
+            }
+        };
         tracing::event!(
             tracing::Level::INFO,
-            "start of simulation of problem `{}` (`{}` method, Δx={:e} ({} steps), Δt={:e} ({} steps))",
+            "start of simulation of problem `{}` (`{}` method, Δx={:e} ({} steps), {})",
             ctx.problem().name,
             ctx.method().name(),
             ctx.mesh().space.delta,
             ctx.mesh().space.steps,
-            ctx.mesh().time.delta,
-            ctx.mesh().time.steps,
+            time_stepping,
         );
         Ok(())
     }
@@ -266,9 +702,11 @@ impl<F: SimpleFloat + std::fmt::LowerExp> Observer<F> for Logger {
     fn at_each_iteration(&mut self, ctx: ObsCtx<F>) -> Result<(), SimError> {
         tracing::event!(
             tracing::Level::TRACE,
-            "problem `{}`: step {}",
+            "problem `{}`: step {} (t={:e}, Δt={:e})",
             ctx.problem().name,
-            ctx.iter()
+            ctx.iter(),
+            ctx.time(),
+            ctx.time_step(),
         );
         Ok(())
     }
@@ -285,6 +723,11 @@ impl<F: SimpleFloat + std::fmt::LowerExp> Observer<F> for Logger {
 
 const CSFF1_HEADER: &[u8] = b"CSFF1";
 
+/// Writes the sampled solutions in the Conlaw Solution File Format v1, read by `vis.py`
+///
+/// A header (dimensions, bounds and method name) is followed by one record per sample, made of
+/// its time and the sampled cells, and by an end marker. The time of each sample is written as
+/// the number of time steps in the header is only nominal under [`TimeStepping::Cfl`].
 pub struct Csff1Writer<W> {
     output: W,
 }
@@ -326,6 +769,8 @@ impl<F: SimpleFloat, W: Write> Observer<F> for Csff1Writer<W> {
     }
 
     fn at_each_iteration(&mut self, ctx: ObsCtx<F>) -> Result<(), SimError> {
+        self.output.write_all(bytes_of(&ctx.time))?;
+
         let u = ctx.solution;
         if ctx.space_sampling == 1 {
             // SAFETY: faer stores matrix contiguously in column major order
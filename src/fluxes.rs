@@ -0,0 +1,562 @@
+use faer_core::{zipped, Mat, MatMut, MatRef};
+use reborrow::*;
+
+use crate::{
+    linalg,
+    riemann::{self, RiemannSolver},
+    ConservationLaw, SimpleFloat,
+};
+
+/// Numerical flux `F(u_L, u_R)` through the interface between two cells, for use with
+/// [`methods::FiniteVolume`](crate::methods::FiniteVolume)
+pub trait NumericalFlux<F: SimpleFloat> {
+    /// `left` and `right` are single cells of `system_size` rows, the flux at the interface is
+    /// written into `flux`
+    fn flux(&self, cl: &dyn ConservationLaw<F>, left: MatRef<F>, right: MatRef<F>, flux: MatMut<F>);
+
+    fn name(&self) -> &'static str;
+}
+
+/// Smallest and largest eigenvalues of the flux Jacobian at `u`
+pub(crate) fn wave_speed_bounds<F: SimpleFloat>(
+    cl: &dyn ConservationLaw<F>,
+    u: MatRef<F>,
+) -> (F, F) {
+    let m = cl.system_size();
+    let mut values = Mat::<F>::zeros(m, 1);
+    cl.eigenvalues(u, values.as_mut());
+    (values.read(0, 0), values.read(m - 1, 0))
+}
+
+/// Exact or approximate Godunov flux given by a Riemann solver
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Godunov<RS>(pub RS);
+
+impl<F: SimpleFloat, RS: RiemannSolver<F>> NumericalFlux<F> for Godunov<RS> {
+    fn flux(
+        &self,
+        cl: &dyn ConservationLaw<F>,
+        left: MatRef<F>,
+        right: MatRef<F>,
+        flux: MatMut<F>,
+    ) {
+        self.0.interface_flux(cl, left, right, flux)
+    }
+
+    fn name(&self) -> &'static str {
+        "Godunov"
+    }
+}
+
+/// Rusanov (local Lax-Friedrichs) flux, with the largest wave speed of both states as numerical
+/// viscosity
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rusanov;
+
+impl<F: SimpleFloat> NumericalFlux<F> for Rusanov {
+    fn flux(
+        &self,
+        cl: &dyn ConservationLaw<F>,
+        left: MatRef<F>,
+        right: MatRef<F>,
+        flux: MatMut<F>,
+    ) {
+        let m = cl.system_size();
+        let mut fl = Mat::<F>::zeros(m, 1);
+        let mut fr = Mat::<F>::zeros(m, 1);
+        cl.flux_function(left, fl.as_mut());
+        cl.flux_function(right, fr.as_mut());
+
+        let (sl, sr) = (cl.max_wave_speed(left), cl.max_wave_speed(right));
+        let s = if sl > sr { sl } else { sr };
+
+        let half = F::from_f64(0.5);
+        zipped!(flux, left, right, fl.as_ref(), fr.as_ref()).for_each(|mut f, ul, ur, fl, fr| {
+            f.write(
+                fl.read()
+                    .add(fr.read())
+                    .sub(s.mul(ur.read().sub(ul.read())))
+                    .mul(half),
+            )
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "Rusanov"
+    }
+}
+
+/// Harten-Lax-van Leer flux, with Davis' estimates of the slowest and fastest wave speeds
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Hll;
+
+impl<F: SimpleFloat> NumericalFlux<F> for Hll {
+    fn flux(
+        &self,
+        cl: &dyn ConservationLaw<F>,
+        left: MatRef<F>,
+        right: MatRef<F>,
+        mut flux: MatMut<F>,
+    ) {
+        let (min_l, max_l) = wave_speed_bounds(cl, left);
+        let (min_r, max_r) = wave_speed_bounds(cl, right);
+        let sl = if min_l < min_r { min_l } else { min_r };
+        let sr = if max_l > max_r { max_l } else { max_r };
+
+        if sl >= F::zero() {
+            return cl.flux_function(left, flux);
+        }
+        if sr <= F::zero() {
+            return cl.flux_function(right, flux);
+        }
+
+        let m = cl.system_size();
+        let mut fl = Mat::<F>::zeros(m, 1);
+        let mut fr = Mat::<F>::zeros(m, 1);
+        cl.flux_function(left, fl.as_mut());
+        cl.flux_function(right, fr.as_mut());
+
+        zipped!(flux.as_mut(), left, right, fl.as_ref(), fr.as_ref()).for_each(
+            |mut f, ul, ur, fl, fr| {
+                f.write(
+                    sr.mul(fl.read())
+                        .sub(sl.mul(fr.read()))
+                        .add(sl.mul(sr).mul(ur.read().sub(ul.read())))
+                        .div(sr.sub(sl)),
+                )
+            },
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "HLL"
+    }
+}
+
+const SIGN_CHANGE_SAMPLES: usize = 16;
+
+/// Engquist-Osher flux `F = f(u_L) + ∫ min(f'(u), 0) du` for scalar conservation laws
+///
+/// The integral is evaluated exactly from the flux differences over the intervals where `f'` is
+/// negative, whose ends are located by sampling `f'` and refining sign changes by bisection.
+///
+/// # Panics
+///
+/// On a system.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EngquistOsher;
+
+impl<F: SimpleFloat> NumericalFlux<F> for EngquistOsher {
+    fn flux(
+        &self,
+        cl: &dyn ConservationLaw<F>,
+        left: MatRef<F>,
+        right: MatRef<F>,
+        mut flux: MatMut<F>,
+    ) {
+        assert_eq!(
+            cl.system_size(),
+            1,
+            "the Engquist-Osher flux needs a scalar conservation law"
+        );
+        let (ul, ur) = (left.read(0, 0), right.read(0, 0));
+        let step = ur.sub(ul).div(F::from_f64(SIGN_CHANGE_SAMPLES as f64));
+
+        let mut f = riemann::scalar_flux(cl, ul);
+        let mut a = ul;
+        for k in 1..=SIGN_CHANGE_SAMPLES {
+            let b = if k == SIGN_CHANGE_SAMPLES {
+                ur
+            } else {
+                ul.add(step.mul(F::from_f64(k as f64)))
+            };
+            let (sa, sb) = (riemann::scalar_speed(cl, a), riemann::scalar_speed(cl, b));
+
+            // portion of [a, b] where f' < 0
+            let (start, end) = match (sa < F::zero(), sb < F::zero()) {
+                (true, true) => (a, b),
+                (false, false) => (a, a),
+                (true, false) => (a, riemann::sonic_point(cl, a, b)),
+                (false, true) => (riemann::sonic_point(cl, a, b), b),
+            };
+            f = f
+                .add(riemann::scalar_flux(cl, end))
+                .sub(riemann::scalar_flux(cl, start));
+            a = b;
+        }
+
+        flux.write(0, 0, f);
+    }
+
+    fn name(&self) -> &'static str {
+        "Engquist-Osher"
+    }
+}
+
+/// Averaged state at which [`Roe`] linearises the conservation law
+pub trait RoeAverage<F: SimpleFloat> {
+    /// `left`, `right` and `average` are single cells of `system_size` rows
+    fn average(
+        &self,
+        cl: &dyn ConservationLaw<F>,
+        left: MatRef<F>,
+        right: MatRef<F>,
+        average: MatMut<F>,
+    );
+}
+
+/// Arithmetic mean `(u_L + u_R)/2` of the states, a fallback when no Roe average is known
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ArithmeticAverage;
+
+impl<F: SimpleFloat> RoeAverage<F> for ArithmeticAverage {
+    fn average(
+        &self,
+        _cl: &dyn ConservationLaw<F>,
+        left: MatRef<F>,
+        right: MatRef<F>,
+        average: MatMut<F>,
+    ) {
+        let half = F::from_f64(0.5);
+        zipped!(average, left, right)
+            .for_each(|mut a, ul, ur| a.write(ul.read().add(ur.read()).mul(half)))
+    }
+}
+
+/// Velocity `m/ρ` weighted by `√ρ`, and the weights, of the states `left` and `right` whose
+/// first two components are a density `ρ` and a momentum `m`, zero for empty states
+fn sqrt_density_weights<F: SimpleFloat>(left: MatRef<F>, right: MatRef<F>) -> (F, F, F) {
+    let weight = |u: MatRef<F>| {
+        let rho = u.read(0, 0);
+        if rho > F::zero() {
+            rho.sqrt()
+        } else {
+            F::zero()
+        }
+    };
+    let velocity = |u: MatRef<F>, w: F| {
+        if w > F::zero() {
+            u.read(1, 0).div(u.read(0, 0))
+        } else {
+            F::zero()
+        }
+    };
+    let (wl, wr) = (weight(left), weight(right));
+    let sum = wl.add(wr);
+    let velocity = if sum > F::zero() {
+        wl.mul(velocity(left, wl))
+            .add(wr.mul(velocity(right, wr)))
+            .div(sum)
+    } else {
+        F::zero()
+    };
+    (velocity, wl, wr)
+}
+
+/// Pressure `p = f_2(u) - m²/ρ` of the Euler state `u` recovered from the momentum flux of `cl`,
+/// its flux being written into `f`
+fn euler_pressure<F: SimpleFloat>(
+    cl: &dyn ConservationLaw<F>,
+    u: MatRef<F>,
+    mut f: MatMut<F>,
+) -> F {
+    cl.flux_function(u, f.rb_mut());
+    f.read(1, 0).sub(u.read(1, 0).mul(euler_velocity(u)))
+}
+
+/// Velocity of a state `u` of the Euler equations, zero in vacuum
+fn euler_velocity<F: SimpleFloat>(u: MatRef<F>) -> F {
+    let rho = u.read(0, 0);
+    if rho > F::zero() {
+        u.read(1, 0).div(rho)
+    } else {
+        F::zero()
+    }
+}
+
+/// Roe average of the [`cl::Euler`](crate::cl::Euler) equations: `√ρ`-weighted velocity and total
+/// specific enthalpy `H = (E + p)/ρ`, at the density `√(ρ_L ρ_R)`
+///
+/// Pressures and the averaged total energy are recovered from the flux of the law the average is
+/// applied to, hence from its equation of state. The linearisation is exact (the Jacobian at the
+/// average maps `u_R - u_L` to `f(u_R) - f(u_L)`) for equations of state affine in `ρe` with
+/// constant coefficients, such as [`cl::IdealGas`](crate::cl::IdealGas) and
+/// [`cl::StiffenedGas`](crate::cl::StiffenedGas). Falls back to the [`ArithmeticAverage`] next to
+/// vacuum.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EulerRoeAverage;
+
+impl<F: SimpleFloat> RoeAverage<F> for EulerRoeAverage {
+    fn average(
+        &self,
+        cl: &dyn ConservationLaw<F>,
+        left: MatRef<F>,
+        right: MatRef<F>,
+        mut average: MatMut<F>,
+    ) {
+        if !(left.read(0, 0) > F::zero() && right.read(0, 0) > F::zero()) {
+            return ArithmeticAverage.average(cl, left, right, average);
+        }
+
+        let mut f = Mat::<F>::zeros(3, 1);
+        let mut pressure = |u: MatRef<F>| euler_pressure(cl, u, f.as_mut());
+        let enthalpy_l = left.read(2, 0).add(pressure(left)).div(left.read(0, 0));
+        let enthalpy_r = right.read(2, 0).add(pressure(right)).div(right.read(0, 0));
+
+        let (velocity, wl, wr) = sqrt_density_weights(left, right);
+        let enthalpy = wl.mul(enthalpy_l).add(wr.mul(enthalpy_r)).div(wl.add(wr));
+        let density = wl.mul(wr);
+        let momentum = density.mul(velocity);
+        average.write(0, 0, density);
+        average.write(1, 0, momentum);
+
+        // total energy such that E + p(E) = ρH, p being affine in E: from the pressures at the
+        // kinetic energy alone and at E = ρH
+        let target = density.mul(enthalpy);
+        let kinetic = momentum.mul(velocity).mul(F::from_f64(0.5));
+        let mut excess = |e: F| {
+            average.write(2, 0, e);
+            e.add(pressure(average.rb())).sub(target)
+        };
+        let (low, high) = (excess(kinetic), excess(target));
+        let energy = if high == low {
+            target
+        } else {
+            kinetic.sub(low.mul(target.sub(kinetic)).div(high.sub(low)))
+        };
+        average.write(2, 0, energy);
+    }
+}
+
+/// Roe average of the [`cl::ShallowWater`](crate::cl::ShallowWater) equations: arithmetic mean of
+/// the depths and `√h`-weighted velocity, an exact linearisation whatever the gravity
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShallowWaterRoeAverage;
+
+impl<F: SimpleFloat> RoeAverage<F> for ShallowWaterRoeAverage {
+    fn average(
+        &self,
+        _cl: &dyn ConservationLaw<F>,
+        left: MatRef<F>,
+        right: MatRef<F>,
+        mut average: MatMut<F>,
+    ) {
+        let depth = left.read(0, 0).add(right.read(0, 0)).mul(F::from_f64(0.5));
+        let (velocity, _, _) = sqrt_density_weights(left, right);
+        average.write(0, 0, depth);
+        average.write(1, 0, depth.mul(velocity));
+    }
+}
+
+/// Roe flux `(f(u_L) + f(u_R))/2 - R |Λ| L (u_R - u_L)/2`, with the eigen-structure of the flux
+/// Jacobian taken at the state given by `A`, and Harten-Hyman's entropy fix for transonic
+/// rarefactions unless disabled
+///
+/// Where the flux Jacobian at the average is not diagonalizable with real eigenvalues, such as
+/// between dry shallow-water states, the flux falls back to [`Hll`].
+#[derive(Debug, Clone, Copy)]
+pub struct Roe<A = ArithmeticAverage> {
+    average: A,
+    entropy_fix: bool,
+}
+
+impl<A: Default> Default for Roe<A> {
+    fn default() -> Self {
+        Self::new(A::default())
+    }
+}
+
+impl<A> Roe<A> {
+    pub fn new(average: A) -> Self {
+        Self {
+            average,
+            entropy_fix: true,
+        }
+    }
+
+    pub fn without_entropy_fix(self) -> Self {
+        Self {
+            entropy_fix: false,
+            ..self
+        }
+    }
+}
+
+impl<F: SimpleFloat, A: RoeAverage<F>> NumericalFlux<F> for Roe<A> {
+    fn flux(
+        &self,
+        cl: &dyn ConservationLaw<F>,
+        left: MatRef<F>,
+        right: MatRef<F>,
+        mut flux: MatMut<F>,
+    ) {
+        let m = cl.system_size();
+        let mut average = Mat::<F>::zeros(m, 1);
+        self.average.average(cl, left, right, average.as_mut());
+        let Some(eigen) = cl.eigen_decomposition(average.as_ref()) else {
+            return Hll.flux(cl, left, right, flux);
+        };
+
+        let mut lambda_l = Mat::<F>::zeros(m, 1);
+        let mut lambda_r = Mat::<F>::zeros(m, 1);
+        if self.entropy_fix {
+            cl.eigenvalues(left, lambda_l.as_mut());
+            cl.eigenvalues(right, lambda_r.as_mut());
+        }
+
+        // upwinding term R |Λ| α, with α = L (u_R - u_L) the wave strengths
+        let mut dissipation = Mat::<F>::zeros(m, 1);
+        for k in 0..m {
+            let alpha = (0..m).fold(F::zero(), |acc, j| {
+                acc.add(
+                    eigen
+                        .left
+                        .read(k, j)
+                        .mul(right.read(j, 0).sub(left.read(j, 0))),
+                )
+            });
+
+            let lambda = eigen.values.read(k, 0);
+            let mut speed = linalg::abs(lambda);
+            if self.entropy_fix {
+                let delta = [
+                    F::zero(),
+                    lambda.sub(lambda_l.read(k, 0)),
+                    lambda_r.read(k, 0).sub(lambda),
+                ]
+                .into_iter()
+                .fold(F::zero(), |max, d| if d > max { d } else { max });
+                if speed < delta {
+                    speed = lambda
+                        .mul(lambda)
+                        .add(delta.mul(delta))
+                        .div(delta.add(delta));
+                }
+            }
+
+            for j in 0..m {
+                dissipation.write(
+                    j,
+                    0,
+                    dissipation
+                        .read(j, 0)
+                        .add(speed.mul(alpha).mul(eigen.right.read(j, k))),
+                );
+            }
+        }
+
+        let mut fr = Mat::<F>::zeros(m, 1);
+        cl.flux_function(left, flux.as_mut());
+        cl.flux_function(right, fr.as_mut());
+
+        let half = F::from_f64(0.5);
+        zipped!(flux, fr.as_ref(), dissipation.as_ref())
+            .for_each(|mut f, fr, d| f.write(f.read().add(fr.read()).sub(d.read()).mul(half)))
+    }
+
+    fn name(&self) -> &'static str {
+        "Roe"
+    }
+}
+
+/// Harten-Lax-van Leer-Contact flux for the [`cl::Euler`](crate::cl::Euler) equations, restoring
+/// the contact wave smeared by [`Hll`]
+///
+/// The pressures and Davis' wave speed estimates come from the flux and the eigenvalues of the law
+/// the flux is applied to, hence from its own equation of state.
+///
+/// # Panics
+///
+/// If the law does not have three components.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Hllc;
+
+impl Hllc {
+    /// Writes into `star` the intermediate state of the wave of speed `s` bounding `u` at the
+    /// pressure `p`, the contact travelling at `s_star`
+    fn star_state<F: SimpleFloat>(u: MatRef<F>, p: F, s: F, s_star: F, mut star: MatMut<F>) {
+        let (rho, e) = (u.read(0, 0), u.read(2, 0));
+        if rho <= F::zero() {
+            // the vacuum stays one across the wave
+            return star.fill_zeros();
+        }
+        let velocity = euler_velocity(u);
+        let factor = rho.mul(s.sub(velocity)).div(s.sub(s_star));
+
+        star.write(0, 0, factor);
+        star.write(1, 0, factor.mul(s_star));
+        star.write(
+            2,
+            0,
+            factor.mul(
+                e.div(rho).add(
+                    s_star
+                        .sub(velocity)
+                        .mul(s_star.add(p.div(rho.mul(s.sub(velocity))))),
+                ),
+            ),
+        );
+    }
+}
+
+impl<F: SimpleFloat> NumericalFlux<F> for Hllc {
+    fn flux(
+        &self,
+        cl: &dyn ConservationLaw<F>,
+        left: MatRef<F>,
+        right: MatRef<F>,
+        mut flux: MatMut<F>,
+    ) {
+        assert_eq!(
+            cl.system_size(),
+            3,
+            "the HLLC flux needs the Euler equations"
+        );
+        let (rho_l, rho_r) = (left.read(0, 0), right.read(0, 0));
+        let (u_l, u_r) = (euler_velocity(left), euler_velocity(right));
+        let (mut fl, mut fr) = (Mat::<F>::zeros(3, 1), Mat::<F>::zeros(3, 1));
+        let (p_l, p_r) = (
+            euler_pressure(cl, left, fl.as_mut()),
+            euler_pressure(cl, right, fr.as_mut()),
+        );
+
+        // Davis' wave speed estimates
+        let (mut lambda_l, mut lambda_r) = (Mat::<F>::zeros(3, 1), Mat::<F>::zeros(3, 1));
+        cl.eigenvalues(left, lambda_l.as_mut());
+        cl.eigenvalues(right, lambda_r.as_mut());
+        let (sl, sr) = (lambda_l.read(0, 0), lambda_r.read(0, 0));
+        let sl = if sl < sr { sl } else { sr };
+        let (a, b) = (lambda_l.read(2, 0), lambda_r.read(2, 0));
+        let sr = if a > b { a } else { b };
+
+        if sl >= F::zero() {
+            return flux.clone_from(fl.as_ref());
+        }
+        if sr <= F::zero() {
+            return flux.clone_from(fr.as_ref());
+        }
+
+        let (ml, mr) = (rho_l.mul(sl.sub(u_l)), rho_r.mul(sr.sub(u_r)));
+        let s_star = p_r
+            .sub(p_l)
+            .add(ml.mul(u_l))
+            .sub(mr.mul(u_r))
+            .div(ml.sub(mr));
+
+        // F*_K = F_K + s_K (U*_K - U_K)
+        let (u, p, f, s) = if s_star >= F::zero() {
+            (left, p_l, fl, sl)
+        } else {
+            (right, p_r, fr, sr)
+        };
+        let mut star = Mat::<F>::zeros(3, 1);
+        Self::star_state(u, p, s, s_star, star.as_mut());
+        flux.clone_from(f.as_ref());
+        zipped!(flux, star.as_ref(), u)
+            .for_each(|mut f, us, u| f.write(f.read().add(s.mul(us.read().sub(u.read())))))
+    }
+
+    fn name(&self) -> &'static str {
+        "HLLC"
+    }
+}
@@ -1,9 +1,10 @@
 #![allow(clippy::pedantic)]
 
-use faer_core::{MatRef, RealField, SimpleEntity};
+use faer_core::{MatMut, MatRef, RealField, SimpleEntity};
 use reborrow::*;
 
 mod driver;
+mod linalg;
 mod mesh;
 mod method;
 // mod problem;
@@ -11,11 +12,16 @@ mod problem;
 mod sim;
 
 pub use driver::*;
+pub use mesh::{Grid, Mesh};
 pub use method::*;
 pub use problem::*;
 pub use sim::*;
 pub mod bc;
+pub mod cl;
+pub mod fluxes;
 pub mod methods;
+pub mod reference;
+pub mod riemann;
 
 pub trait SimpleFloat: RealField + SimpleEntity + Default {}
 impl<T> SimpleFloat for T where T: RealField + SimpleEntity + Default {}
@@ -30,38 +36,129 @@ pub struct Ctx<'ctx, F: SimpleFloat> {
     /// Number of right ghost cells
     pub right_ghost_cells: usize,
 
+    /// Problem being solved
+    pub problem: &'ctx Problem<'ctx, F>,
     /// Mesh
     pub mesh: &'ctx mesh::Mesh<F>,
     /// Current time step
     pub n: usize,
-    /// Current time value
+    /// Time value reached at the end of the current step
     pub t: F,
+    /// Length Δt of the current time step
+    pub dt: F,
+    /// How the source of the problem, if any, is integrated
+    pub(crate) splitting: Splitting,
     /// Whole solution, including ghost cells
     u: MatRef<'ctx, F>,
 }
 
-impl<F: SimpleFloat> Ctx<'_, F> {
-    pub(crate) fn slide(&self, p: isize) -> MatRef<F> {
-        self.u.rb().subrows(
-            self.left_ghost_cells
-                .saturating_add_signed(p * self.system_size as isize),
-            self.mesh.space.steps + 1,
+impl<'ctx, F: SimpleFloat> Ctx<'ctx, F> {
+    /// Solution shifted by `p` cells, `p` ranging from `-left_ghost_cells` to `right_ghost_cells`:
+    /// the cell `i` of the result is the cell `i + p` of the domain, a ghost cell when out of it
+    ///
+    /// Methods reach this way the whole stencil their ghost cells allow, beyond the neighbours
+    /// given by [`left`](Self::left), [`left2`](Self::left2), [`right`](Self::right) and
+    /// [`right2`](Self::right2). For instance, a scheme copying the solution three cells upwind,
+    /// exact for the advection `u_t + u_x = 0` when `Δt = 3Δx`:
+    ///
+    /// ```
+    /// use std::rc::Rc;
+    ///
+    /// use faer_core::{MatMut, MatRef};
+    ///
+    /// use conlaw::{ConservationLaw, Ctx, Method};
+    ///
+    /// struct ThreeCellShift;
+    ///
+    /// impl Method<f64> for ThreeCellShift {
+    ///     fn left_ghost_cells(&self) -> usize {
+    ///         3
+    ///     }
+    ///
+    ///     fn right_ghost_cells(&self) -> usize {
+    ///         0
+    ///     }
+    ///
+    ///     fn init(&mut self, _ctx: Ctx<f64>) {}
+    ///
+    ///     fn apply<'m, 'pb>(
+    ///         &'m mut self,
+    ///         ctx: Ctx<f64>,
+    ///         _flux: Rc<dyn ConservationLaw<f64> + 'pb>,
+    ///         _u: MatRef<f64>,
+    ///         mut v: MatMut<f64>,
+    ///     ) {
+    ///         v.clone_from(ctx.slide(-3));
+    ///     }
+    ///
+    ///     fn name(&self) -> &'static str {
+    ///         "Three-cell shift"
+    ///     }
+    ///
+    ///     fn cfl_limit(&self) -> f64 {
+    ///         3.
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `p` is out of range.
+    pub fn slide(&self, p: isize) -> MatRef<'_, F> {
+        self.slide_of(self.u.rb(), p)
+    }
+
+    /// Same as [`slide`](Self::slide) within a buffer `x` laid out like the whole solution,
+    /// ghost cells included
+    ///
+    /// # Panics
+    ///
+    /// If `p` is out of range.
+    pub fn slide_of<'a>(&self, x: MatRef<'a, F>, p: isize) -> MatRef<'a, F> {
+        let (left, right) = (self.left_ghost_cells as isize, self.right_ghost_cells as isize);
+        assert!(
+            (-left..=right).contains(&p),
+            "cannot slide by {p} cells with {left} left and {right} right ghost cells"
+        );
+        x.subrows(
+            (left + p) as usize * self.system_size,
+            (self.mesh.space.steps + 1) * self.system_size,
         )
     }
 
-    pub fn left(&self) -> MatRef<F> {
+    /// Context of an intermediate stage, whose whole solution (ghost cells included) is `u`
+    pub(crate) fn with_solution<'a>(&self, u: MatRef<'a, F>) -> Ctx<'a, F>
+    where
+        'ctx: 'a,
+    {
+        Ctx { u, ..*self }
+    }
+
+    /// Fills the ghost cells of `u`, a whole solution at time `t`, with the problem's boundary
+    /// condition
+    pub(crate) fn fill_ghost_cells(&self, t: F, u: MatMut<'_, F>) {
+        let left_count = self.left_ghost_cells * self.system_size;
+        let center_count = (self.mesh.space.steps + 1) * self.system_size;
+        let [left, right] = u.split_at_row(left_count);
+        let [center, right] = right.split_at_row(center_count);
+        self.problem
+            .bc
+            .apply(Ctx { t, ..*self }, left, center.rb(), right);
+    }
+
+    pub fn left(&self) -> MatRef<'_, F> {
         self.slide(-1)
     }
 
-    pub fn left2(&self) -> MatRef<F> {
+    pub fn left2(&self) -> MatRef<'_, F> {
         self.slide(-2)
     }
 
-    pub fn right(&self) -> MatRef<F> {
+    pub fn right(&self) -> MatRef<'_, F> {
         self.slide(1)
     }
 
-    pub fn right2(&self) -> MatRef<F> {
+    pub fn right2(&self) -> MatRef<'_, F> {
         self.slide(2)
     }
 }
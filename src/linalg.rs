@@ -0,0 +1,335 @@
+//! Dense linear algebra on the small matrices arising from flux Jacobians, on top of the QR, SVD
+//! and LU decompositions of `faer`
+//!
+//! The general eigendecomposition of `faer` 0.12 breaks down on 2×2 blocks with equal diagonal
+//! entries, common in flux Jacobians (e.g. `[[0, 1], [c², 0]]`), so eigenvalues come from a
+//! shifted QR iteration and eigenvectors from null spaces instead.
+
+use faer::solvers::{PartialPivLu, Qr, SolverCore, Svd};
+use faer_core::{mul, Mat, MatRef, Parallelism};
+
+use crate::{EigenDecomposition, SimpleFloat};
+
+const MAX_QR_ITERATIONS: usize = 100;
+
+pub(crate) fn epsilon<F: SimpleFloat>() -> F {
+    F::epsilon().unwrap_or(F::from_f64(f64::EPSILON))
+}
+
+pub(crate) fn abs<F: SimpleFloat>(x: F) -> F {
+    if x < F::zero() {
+        x.neg()
+    } else {
+        x
+    }
+}
+
+/// Largest number of terms of the power series of [`exp`] and [`ln`], far more than the
+/// precision of `F` needs for their reduced arguments
+const MAX_SERIES_TERMS: usize = 100;
+
+/// `atanh(s) = s + s³/3 + s⁵/5 + …`, for `|s|` small enough
+fn atanh_series<F: SimpleFloat>(s: F) -> F {
+    let (s2, eps) = (s.mul(s), epsilon::<F>());
+    let (mut power, mut sum) = (s, s);
+    for k in 1..MAX_SERIES_TERMS {
+        power = power.mul(s2);
+        let term = power.div(F::from_f64((2 * k + 1) as f64));
+        sum = sum.add(term);
+        if abs(term) <= eps.mul(abs(sum)) {
+            break;
+        }
+    }
+    sum
+}
+
+/// `ln 2 = 2 atanh(1/3)`, to the precision of `F`
+fn ln_2<F: SimpleFloat>() -> F {
+    F::from_f64(2.).mul(atanh_series(F::one().div(F::from_f64(3.))))
+}
+
+/// `2^k`, by squaring
+fn power_of_two<F: SimpleFloat>(k: i64) -> F {
+    let (mut base, mut result, mut bits) = (F::from_f64(2.), F::one(), k.unsigned_abs());
+    while bits > 0 {
+        if bits & 1 == 1 {
+            result = result.mul(base);
+        }
+        base = base.mul(base);
+        bits >>= 1;
+    }
+    if k < 0 {
+        F::one().div(result)
+    } else {
+        result
+    }
+}
+
+/// Natural logarithm, `-∞` at 0 and NaN below
+///
+/// The argument is reduced to `m 2^k` with `m` within `[1/√2, √2]`, whose logarithm is
+/// `2 atanh((m - 1)/(m + 1))`.
+pub(crate) fn ln<F: SimpleFloat>(x: F) -> F {
+    if x.is_nan() || x < F::zero() {
+        return F::nan();
+    }
+    if x == F::zero() {
+        return F::one().neg().div(F::zero());
+    }
+    if !x.is_finite() {
+        return x;
+    }
+
+    let (mut m, mut k) = (x, 0i64);
+    for bits in [64, 8, 1] {
+        let step = power_of_two::<F>(bits);
+        while m > step {
+            m = m.div(step);
+            k += bits;
+        }
+        while m.mul(step) < F::one() {
+            m = m.mul(step);
+            k -= bits;
+        }
+    }
+    let sqrt_2 = F::from_f64(2.).sqrt();
+    if m > sqrt_2 {
+        m = m.div(F::from_f64(2.));
+        k += 1;
+    } else if m.mul(sqrt_2) < F::one() {
+        m = m.mul(F::from_f64(2.));
+        k -= 1;
+    }
+
+    let s = m.sub(F::one()).div(m.add(F::one()));
+    F::from_f64(k as f64)
+        .mul(ln_2())
+        .add(F::from_f64(2.).mul(atanh_series(s)))
+}
+
+/// Exponential
+///
+/// The argument is reduced to `r + k ln 2` with `|r| <= ln 2 / 2`, the bits of the integer `k`
+/// being found from the most significant one, and `exp(r)` summed from its power series.
+pub(crate) fn exp<F: SimpleFloat>(x: F) -> F {
+    if x.is_nan() {
+        return x;
+    }
+    if !x.is_finite() {
+        return if x > F::zero() { x } else { F::zero() };
+    }
+
+    let ln_2 = ln_2::<F>();
+    let q = abs(x.div(ln_2));
+    let mut bit = 1f64;
+    while bit < 2f64.powi(62) && F::from_f64(2. * bit) <= q {
+        bit *= 2.;
+    }
+    let mut k = 0f64;
+    while bit >= 1. {
+        if F::from_f64(k + bit) <= q {
+            k += bit;
+        }
+        bit /= 2.;
+    }
+    if q.sub(F::from_f64(k)) > F::from_f64(0.5) {
+        k += 1.;
+    }
+    let k = if x < F::zero() { -k } else { k };
+    let r = x.sub(F::from_f64(k).mul(ln_2));
+
+    let eps = epsilon::<F>();
+    let (mut term, mut sum) = (F::one(), F::one());
+    for n in 1..MAX_SERIES_TERMS {
+        term = term.mul(r).div(F::from_f64(n as f64));
+        sum = sum.add(term);
+        if abs(term) <= eps.mul(sum) {
+            break;
+        }
+    }
+    sum.mul(power_of_two(k as i64))
+}
+
+/// `x^y = exp(y ln x)` for `x >= 0`, 0 at `x = 0` for `y > 0`
+pub(crate) fn powf<F: SimpleFloat>(x: F, y: F) -> F {
+    if x == F::zero() && y > F::zero() {
+        return F::zero();
+    }
+    exp(y.mul(ln(x)))
+}
+
+/// Step for the central difference approximation of a derivative at `x`
+pub(crate) fn difference_step<F: SimpleFloat>(x: F) -> F {
+    let scale = abs(x);
+    let scale = if scale > F::one() { scale } else { F::one() };
+    epsilon::<F>().sqrt().mul(scale)
+}
+
+/// Largest absolute value of the entries of `a`, NaN if one of them is
+fn norm_max<F: SimpleFloat>(a: MatRef<F>) -> F {
+    let mut norm = F::zero();
+    for j in 0..a.ncols() {
+        for i in 0..a.nrows() {
+            let x = abs(a.read(i, j));
+            if x > norm || x.is_nan() {
+                norm = x;
+            }
+        }
+    }
+    norm
+}
+
+/// Accuracy `δ = √ε` of flux Jacobians, approximated by finite differences by default, at the
+/// scale of `a`
+fn accuracy<F: SimpleFloat>(a: MatRef<F>) -> F {
+    epsilon::<F>().sqrt().mul(norm_max(a).add(F::one()))
+}
+
+/// Distance under which eigenvalues of `a` are considered equal: a perturbation `δ` splits a
+/// double eigenvalue into a pair up to `√δ` apart, possibly complex
+fn spread<F: SimpleFloat>(a: MatRef<F>) -> F {
+    epsilon::<F>().sqrt().sqrt().mul(norm_max(a).add(F::one()))
+}
+
+pub(crate) fn matmul<F: SimpleFloat>(a: MatRef<F>, b: MatRef<F>) -> Mat<F> {
+    let mut c = Mat::<F>::zeros(a.nrows(), b.ncols());
+    mul::matmul(c.as_mut(), a, b, None, F::one(), Parallelism::None);
+    c
+}
+
+/// `a - μ I`
+fn shifted<F: SimpleFloat>(a: MatRef<F>, mu: F) -> Mat<F> {
+    Mat::from_fn(a.nrows(), a.ncols(), |i, j| {
+        if i == j {
+            a.read(i, j).sub(mu)
+        } else {
+            a.read(i, j)
+        }
+    })
+}
+
+/// Eigenvalues of `a` in increasing order, computed by the shifted QR algorithm with deflation,
+/// or `None` if some of them are complex or the iteration did not converge
+pub(crate) fn eigenvalues<F: SimpleFloat>(a: MatRef<F>) -> Option<Vec<F>> {
+    let spread = spread(a);
+    let tol = epsilon::<F>().mul(norm_max(a).add(F::one()));
+    let half = F::from_f64(0.5);
+    // whether the first `cols` entries of `row` vanish
+    let decoupled =
+        |a: MatRef<F>, row: usize, cols: usize| (0..cols).all(|j| abs(a.read(row, j)) <= tol);
+
+    let mut a = a.to_owned();
+    let mut values = Vec::with_capacity(a.nrows());
+    'deflation: while a.nrows() > 0 {
+        for _ in 0..MAX_QR_ITERATIONS {
+            let n = a.nrows();
+            if n == 1 || decoupled(a.as_ref(), n - 1, n - 1) {
+                values.push(a.read(n - 1, n - 1));
+                a = a.as_ref().submatrix(0, 0, n - 1, n - 1).to_owned();
+                continue 'deflation;
+            }
+
+            // trailing 2×2 block
+            let (p, q, r, s) = (
+                a.read(n - 2, n - 2),
+                a.read(n - 2, n - 1),
+                a.read(n - 1, n - 2),
+                a.read(n - 1, n - 1),
+            );
+            let mean = p.add(s).mul(half);
+            let gap = p.sub(s).mul(half);
+            let disc = gap.mul(gap).add(q.mul(r));
+
+            if n == 2
+                || (decoupled(a.as_ref(), n - 2, n - 2) && decoupled(a.as_ref(), n - 1, n - 2))
+            {
+                // complex pair, unless split from a double eigenvalue by the inaccuracy of `a`
+                if disc.neg() > spread.mul(spread) {
+                    return None;
+                }
+                let root = if disc > F::zero() {
+                    disc.sqrt()
+                } else {
+                    F::zero()
+                };
+                values.push(mean.sub(root));
+                values.push(mean.add(root));
+                a = a.as_ref().submatrix(0, 0, n - 2, n - 2).to_owned();
+                continue 'deflation;
+            }
+
+            // Wilkinson shift, the eigenvalue of the block closest to the last diagonal entry
+            let mu = if disc < F::zero() {
+                s
+            } else {
+                let root = disc.sqrt();
+                let (l1, l2) = (mean.add(root), mean.sub(root));
+                if abs(l1.sub(s)) < abs(l2.sub(s)) {
+                    l1
+                } else {
+                    l2
+                }
+            };
+            let qr = Qr::new(shifted(a.as_ref(), mu).as_ref());
+            let rq = matmul(qr.compute_r().as_ref(), qr.compute_q().as_ref());
+            a = shifted(rq.as_ref(), mu.neg());
+        }
+        return None;
+    }
+
+    values.sort_by(|x, y| x.partial_cmp(y).unwrap_or(core::cmp::Ordering::Equal));
+    Some(values)
+}
+
+/// Inverse of `a`, or `None` if it is singular or too ill-conditioned to be computed accurately
+fn inverse<F: SimpleFloat>(a: MatRef<F>) -> Option<Mat<F>> {
+    let inv = PartialPivLu::new(a).inverse();
+
+    // condition number estimate `‖A‖ ‖A⁻¹‖`
+    let condition = norm_max(a).mul(norm_max(inv.as_ref()));
+    let error = shifted(matmul(a, inv.as_ref()).as_ref(), F::one());
+    let limit = F::one().div(epsilon::<F>().sqrt());
+    (condition <= limit && norm_max(error.as_ref()) <= accuracy(a)).then_some(inv)
+}
+
+/// Full eigen-structure of `a`, or `None` if some eigenvalues are complex or `a` is not
+/// diagonalizable
+///
+/// The eigenvectors of an eigenvalue of multiplicity `k` are the right singular vectors of
+/// `a - λ I` for its `k` smallest singular values, which must vanish.
+pub(crate) fn eigen_decomposition<F: SimpleFloat>(a: MatRef<F>) -> Option<EigenDecomposition<F>> {
+    let n = a.nrows();
+    let values = eigenvalues(a)?;
+    let spread = spread(a);
+
+    let mut right = Mat::<F>::zeros(n, n);
+    let mut k = 0;
+    while k < n {
+        let multiplicity = values[k..]
+            .iter()
+            .take_while(|&&lambda| lambda.sub(values[k]) <= spread)
+            .count();
+        let lambda = values[k..k + multiplicity]
+            .iter()
+            .fold(F::zero(), |sum, &lambda| sum.add(lambda))
+            .div(F::from_f64(multiplicity as f64));
+
+        let svd = Svd::new(shifted(a, lambda).as_ref());
+        let null = n - multiplicity;
+        if (null..n).any(|i| svd.s_diagonal().read(i, 0) > spread) {
+            return None;
+        }
+        right
+            .as_mut()
+            .subcols(k, multiplicity)
+            .clone_from(svd.v().subcols(null, multiplicity));
+        k += multiplicity;
+    }
+
+    let left = inverse(right.as_ref())?;
+    Some(EigenDecomposition {
+        values: Mat::from_fn(n, 1, |i, _| values[i]),
+        right,
+        left,
+    })
+}
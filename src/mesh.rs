@@ -38,6 +38,22 @@ impl<F: SimpleFloat> Grid<F> {
         Self::from_steps(self.lower, self.upper, steps)
     }
 
+    pub fn lower(&self) -> F {
+        self.lower
+    }
+
+    pub fn upper(&self) -> F {
+        self.upper
+    }
+
+    pub fn delta(&self) -> F {
+        self.delta
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
     pub fn iter(self) -> impl Iterator<Item = F> {
         (0..(self.steps + 1)).map(move |i| self.lower.add(self.delta.mul(F::from_f64(i as f64))))
     }
@@ -53,4 +69,12 @@ impl<F: SimpleFloat> Mesh<F> {
     pub fn new(time: Grid<F>, space: Grid<F>) -> Self {
         Self { time, space }
     }
+
+    pub fn time(&self) -> Grid<F> {
+        self.time
+    }
+
+    pub fn space(&self) -> Grid<F> {
+        self.space
+    }
 }
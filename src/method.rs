@@ -16,6 +16,36 @@ pub trait Method<F: SimpleFloat> {
         v: MatMut<F>,
     );
     fn name(&self) -> &'static str;
+
+    /// Largest CFL number `Δt/Δx · max|f'(u)|` for which the method is stable
+    fn cfl_limit(&self) -> F {
+        F::one()
+    }
+
+    /// Whether the method integrates an unsplit source itself, within its own stages, instead of
+    /// the forward Euler source step added by the driver after [`apply`](Self::apply)
+    fn integrates_source(&self) -> bool {
+        false
+    }
+}
+
+/// Semi-discrete spatial discretization `du/dt = L(u)`, turned into a [`Method`] by a time
+/// integrator, see [`methods::MethodOfLines`](crate::methods::MethodOfLines)
+pub trait SpatialOperator<F: SimpleFloat> {
+    fn left_ghost_cells(&self) -> usize;
+    fn right_ghost_cells(&self) -> usize;
+    fn init(&mut self, ctx: Ctx<F>);
+    /// Writes `L(u)` into `rhs`, `u` being the solution of the Runge-Kutta stage, read through
+    /// [`Ctx::slide`] with its ghost cells filled, and `ctx.t` the time of the stage rather than
+    /// the end of the step
+    fn rhs(&mut self, ctx: Ctx<F>, flux: &dyn ConservationLaw<F>, rhs: MatMut<F>);
+    fn name(&self) -> &'static str;
+
+    /// Largest CFL number for which the operator is stable with the strong stability preserving
+    /// Runge-Kutta integrators
+    fn cfl_limit(&self) -> F {
+        F::one()
+    }
 }
 
 #[derive(Default)]
@@ -29,11 +59,21 @@ impl<F: SimpleFloat, const N: usize> Buffers<F, N> {
         self.inner.resize_with(size, N, |_, _| F::zero())
     }
 
-    pub fn get(&self, n: usize) -> MatRef<F> {
+    pub fn get(&self, n: usize) -> MatRef<'_, F> {
         self.inner.as_ref().col(n)
     }
 
-    pub fn get_mut(&mut self, n: usize) -> MatMut<F> {
+    pub fn get_mut(&mut self, n: usize) -> MatMut<'_, F> {
         self.inner.as_mut().col(n)
     }
+
+    /// All buffers at once, for computations that write to some while reading others
+    pub fn get_all_mut(&mut self) -> [MatMut<'_, F>; N] {
+        let mut rest = Some(self.inner.as_mut());
+        core::array::from_fn(|_| {
+            let [head, tail] = rest.take().unwrap().split_at_col(1);
+            rest = Some(tail);
+            head
+        })
+    }
 }
@@ -0,0 +1,245 @@
+use std::rc::Rc;
+
+use faer_core::{zipped, MatMut, MatRef};
+use reborrow::*;
+
+use super::{Limiter, MethodOfLines};
+use crate::{
+    fluxes::{NumericalFlux, Rusanov},
+    method::{Buffers, Method, SpatialOperator},
+    problem::ConservationLaw,
+    Ctx, SimpleFloat,
+};
+
+/// `count` cells of `x` starting from the cell `first`
+fn cells<F: SimpleFloat>(x: MatRef<'_, F>, m: usize, first: usize, count: usize) -> MatRef<'_, F> {
+    x.subrows(first * m, count * m)
+}
+
+/// Second-order central scheme of Nessyahu and Tadmor, free of Riemann solvers
+///
+/// Each time step is made of two staggered half steps, from the cells to their interfaces and
+/// back, so that the solution stays on the mesh.
+#[derive(Default)]
+pub struct NessyahuTadmor<F: SimpleFloat> {
+    limiter: Limiter,
+    // solution at the interfaces after the first half step, then at the cells after the second,
+    // both laid out like the whole solution
+    staggered: Buffers<F, 2>,
+    // slopes, fluxes, predictors and fluxes of the predictors
+    work: Buffers<F, 4>,
+}
+
+impl<F: SimpleFloat> NessyahuTadmor<F> {
+    pub fn new(limiter: Limiter) -> Self {
+        Self {
+            limiter,
+            staggered: Buffers::default(),
+            work: Buffers::default(),
+        }
+    }
+
+    /// Staggered half step of ratio `r = Δt/2Δx`: `y[j]` is the solution at the interface
+    /// between `x[j]` and `x[j+1]`, for `j` from 1 to the number of cells minus 3
+    fn stagger(
+        limiter: Limiter,
+        work: &mut Buffers<F, 4>,
+        flux: &dyn ConservationLaw<F>,
+        m: usize,
+        r: F,
+        x: MatRef<F>,
+        y: MatMut<F>,
+    ) {
+        let c = |x: f64| F::from_f64(x);
+        let n = x.nrows() / m;
+        let inner = |x, k| cells(x, m, k, n - 2);
+        let [mut s, mut fx, mut p, mut fp] = work.get_all_mut();
+
+        // limited slopes of the solution and of its flux, and predictors at the half step
+        zipped!(
+            s.rb_mut().subrows(m, (n - 2) * m),
+            inner(x, 0),
+            inner(x, 1),
+            inner(x, 2)
+        )
+        .for_each(|mut s, xm, x, xp| {
+            let (xm, x, xp) = (xm.read(), x.read(), xp.read());
+            s.write(limiter.slope(x.sub(xm), xp.sub(x)))
+        });
+        flux.bulk_flux_function(x, fx.rb_mut());
+        zipped!(
+            p.rb_mut().subrows(m, (n - 2) * m),
+            inner(x, 1),
+            inner(fx.rb(), 0),
+            inner(fx.rb(), 1),
+            inner(fx.rb(), 2)
+        )
+        .for_each(|mut p, x, fm, f, fp| {
+            let (fm, f, fp) = (fm.read(), f.read(), fp.read());
+            p.write(
+                x.read()
+                    .sub(r.mul(c(0.5)).mul(limiter.slope(f.sub(fm), fp.sub(f)))),
+            )
+        });
+        flux.bulk_flux_function(
+            p.rb().subrows(m, (n - 2) * m),
+            fp.rb_mut().subrows(m, (n - 2) * m),
+        );
+
+        // staggered averages corrected by the slopes and the fluxes of the predictors
+        let inner = |x, k| cells(x, m, k, n - 3);
+        zipped!(
+            y.subrows(m, (n - 3) * m),
+            inner(x, 1),
+            inner(x, 2),
+            inner(s.rb(), 1),
+            inner(s.rb(), 2),
+            inner(fp.rb(), 1),
+            inner(fp.rb(), 2)
+        )
+        .for_each(|mut y, x, xp, s, sp, f, fp| {
+            let mean = x.read().add(xp.read()).mul(c(0.5));
+            let correction = s.read().sub(sp.read()).mul(c(0.125));
+            y.write(mean.add(correction).sub(r.mul(fp.read().sub(f.read()))))
+        });
+    }
+}
+
+impl<F: SimpleFloat> Method<F> for NessyahuTadmor<F> {
+    fn left_ghost_cells(&self) -> usize {
+        3
+    }
+
+    fn right_ghost_cells(&self) -> usize {
+        3
+    }
+
+    fn init(&mut self, ctx: Ctx<F>) {
+        let center = (ctx.mesh.space.steps + 1) * ctx.system_size;
+        let ghosts = (ctx.left_ghost_cells + ctx.right_ghost_cells) * ctx.system_size;
+        self.staggered.resize(center + ghosts);
+        self.work.resize(center + ghosts);
+    }
+
+    fn apply<'m, 'pb>(
+        &'m mut self,
+        ctx: Ctx<F>,
+        flux: Rc<dyn ConservationLaw<F> + 'pb>,
+        _u: MatRef<F>,
+        mut v: MatMut<F>,
+    ) {
+        let m = ctx.system_size;
+        let r = ctx.dt.div(ctx.mesh.space.delta).mul(F::from_f64(0.5));
+        let [mut y, mut z] = self.staggered.get_all_mut();
+
+        // the interface between the cells j and j+1 of the whole solution lands in y[j], and the
+        // cell j+1 back in z[j], so that the cell i of the domain is z[i+2]
+        Self::stagger(
+            self.limiter,
+            &mut self.work,
+            flux.as_ref(),
+            m,
+            r,
+            ctx.u,
+            y.rb_mut(),
+        );
+        Self::stagger(
+            self.limiter,
+            &mut self.work,
+            flux.as_ref(),
+            m,
+            r,
+            y.rb(),
+            z.rb_mut(),
+        );
+
+        v.clone_from(z.rb().subrows(2 * m, v.nrows()));
+    }
+
+    fn name(&self) -> &'static str {
+        "Nessyahu-Tadmor"
+    }
+
+    fn cfl_limit(&self) -> F {
+        F::from_f64(0.5)
+    }
+}
+
+/// Semi-discrete central scheme of Kurganov and Tadmor: limited piecewise linear reconstruction
+/// with local wave speed estimates at the interfaces, see [`KurganovTadmor`] for the full scheme
+#[derive(Default)]
+pub struct KurganovTadmorOperator<F: SimpleFloat> {
+    limiter: Limiter,
+    // left and right faces of the cells and of the ghost cell on each side
+    faces: Buffers<F, 2>,
+    interfaces: Buffers<F, 1>,
+}
+
+impl<F: SimpleFloat> KurganovTadmorOperator<F> {
+    pub fn new(limiter: Limiter) -> Self {
+        Self {
+            limiter,
+            faces: Buffers::default(),
+            interfaces: Buffers::default(),
+        }
+    }
+}
+
+impl<F: SimpleFloat> SpatialOperator<F> for KurganovTadmorOperator<F> {
+    fn left_ghost_cells(&self) -> usize {
+        2
+    }
+
+    fn right_ghost_cells(&self) -> usize {
+        2
+    }
+
+    fn init(&mut self, ctx: Ctx<F>) {
+        self.faces
+            .resize((ctx.mesh.space.steps + 3) * ctx.system_size);
+        self.interfaces
+            .resize((ctx.mesh.space.steps + 2) * ctx.system_size);
+    }
+
+    fn rhs(&mut self, ctx: Ctx<F>, flux: &dyn ConservationLaw<F>, rhs: MatMut<F>) {
+        let (m, n) = (ctx.system_size, rhs.nrows());
+        let half = F::from_f64(0.5);
+        let limiter = self.limiter;
+
+        // faces of the cells, from the one left of the domain to the one right of it
+        let (first, count) = (ctx.left_ghost_cells - 2, ctx.mesh.space.steps + 3);
+        let [um, u, up] = [0, 1, 2].map(|k| cells(ctx.u, m, first + k, count));
+        for (k, side) in [(0, half.neg()), (1, half)] {
+            zipped!(self.faces.get_mut(k), um, u, up).for_each(|mut v, um, u, up| {
+                let (um, u, up) = (um.read(), u.read(), up.read());
+                v.write(u.add(side.mul(limiter.slope(u.sub(um), up.sub(u)))))
+            });
+        }
+
+        // the Rusanov flux of the reconstructed states is the central flux with local speeds,
+        // between the right face of a cell and the left face of the next one
+        let lefts = self.faces.get(1).subrows(0, n + m).into_row_chunks(m);
+        let rights = self.faces.get(0).subrows(m, n + m).into_row_chunks(m);
+        for (j, (ul, ur)) in lefts.zip(rights).enumerate() {
+            let f = self.interfaces.get_mut(0).subrows(j * m, m);
+            Rusanov.flux(flux, ul, ur, f);
+        }
+
+        let dx = ctx.mesh.space.delta;
+        let interfaces = self.interfaces.get(0);
+        zipped!(rhs, interfaces.subrows(0, n), interfaces.subrows(m, n))
+            .for_each(|mut v, fm, fp| v.write(fm.read().sub(fp.read()).div(dx)));
+    }
+
+    fn name(&self) -> &'static str {
+        "Kurganov-Tadmor"
+    }
+
+    fn cfl_limit(&self) -> F {
+        F::from_f64(0.5)
+    }
+}
+
+/// Kurganov-Tadmor scheme, with the third-order strong stability preserving Runge-Kutta time
+/// integrator by default
+pub type KurganovTadmor<F> = MethodOfLines<F, KurganovTadmorOperator<F>>;
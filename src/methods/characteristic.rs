@@ -0,0 +1,132 @@
+use faer_core::{Mat, MatMut, MatRef};
+
+use super::Limiter;
+use crate::{
+    fluxes::{ArithmeticAverage, EulerRoeAverage, RoeAverage, ShallowWaterRoeAverage},
+    linalg,
+    problem::ConservationLaw,
+    EigenDecomposition, SimpleFloat,
+};
+
+/// State between two cells at which [`Variables::Characteristic`] takes the eigen-structure of
+/// the flux Jacobian
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Average {
+    /// [`ArithmeticAverage`], for any law
+    #[default]
+    Arithmetic,
+    /// [`EulerRoeAverage`], for the [`Euler`](crate::cl::Euler) equations
+    EulerRoe,
+    /// [`ShallowWaterRoeAverage`], for the [`ShallowWater`](crate::cl::ShallowWater) equations
+    ShallowWaterRoe,
+}
+
+impl Average {
+    fn average<F: SimpleFloat>(
+        self,
+        cl: &dyn ConservationLaw<F>,
+        left: MatRef<F>,
+        right: MatRef<F>,
+        average: MatMut<F>,
+    ) {
+        match self {
+            Average::Arithmetic => ArithmeticAverage.average(cl, left, right, average),
+            Average::EulerRoe => EulerRoeAverage.average(cl, left, right, average),
+            Average::ShallowWaterRoe => ShallowWaterRoeAverage.average(cl, left, right, average),
+        }
+    }
+}
+
+/// Reconstruction where the flux Jacobian at the averaged state is not diagonalizable with real
+/// eigenvalues, such as next to dry shallow-water states
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Fallback {
+    /// Component-wise reconstruction of the conserved variables around these cells
+    #[default]
+    Conserved,
+    /// Panics instead, for laws expected to stay strictly hyperbolic
+    Panic,
+}
+
+/// Variables in which high-order methods reconstruct the solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variables {
+    /// Component-wise reconstruction of the conserved variables
+    #[default]
+    Conserved,
+    /// Reconstruction of the characteristic variables `L u`, with the eigenvectors of the flux
+    /// Jacobian at the `average` of neighbouring cells, avoiding the oscillations of
+    /// component-wise reconstruction for systems
+    ///
+    /// The eigenvectors are the law's own
+    /// [`eigen_decomposition`](ConservationLaw::eigen_decomposition), analytic for the laws of
+    /// [`cl`](crate::cl).
+    Characteristic {
+        average: Average,
+        fallback: Fallback,
+    },
+}
+
+impl Variables {
+    /// Characteristic variables at the arithmetic average, falling back to conserved variables
+    pub fn characteristic() -> Self {
+        Variables::Characteristic {
+            average: Average::default(),
+            fallback: Fallback::default(),
+        }
+    }
+
+    /// Eigen-structure used to reconstruct around the averaged state of `left` and `right`, or
+    /// `None` for component-wise reconstruction
+    ///
+    /// # Panics
+    ///
+    /// With [`Fallback::Panic`], if the flux Jacobian is not diagonalizable at the averaged
+    /// state.
+    pub(crate) fn basis<F: SimpleFloat>(
+        self,
+        cl: &dyn ConservationLaw<F>,
+        left: MatRef<F>,
+        right: MatRef<F>,
+    ) -> Option<EigenDecomposition<F>> {
+        match self {
+            Variables::Conserved => None,
+            Variables::Characteristic { average, fallback } => {
+                let mut state = Mat::<F>::zeros(cl.system_size(), 1);
+                average.average(cl, left, right, state.as_mut());
+                let basis = cl.eigen_decomposition(state.as_ref());
+                if basis.is_none() && fallback == Fallback::Panic {
+                    panic!("the flux Jacobian is not diagonalizable at the averaged state");
+                }
+                basis
+            }
+        }
+    }
+}
+
+/// Limited slope of the cell `u`, between the cells `um` and `up`, computed on the
+/// characteristic `variables` at the average of `um` and `up` and projected back
+pub(crate) fn characteristic_slope<F: SimpleFloat>(
+    variables: Variables,
+    limiter: Limiter,
+    cl: &dyn ConservationLaw<F>,
+    um: MatRef<F>,
+    u: MatRef<F>,
+    up: MatRef<F>,
+) -> Mat<F> {
+    let m = u.nrows();
+    let backward = Mat::from_fn(m, 1, |i, _| u.read(i, 0).sub(um.read(i, 0)));
+    let forward = Mat::from_fn(m, 1, |i, _| up.read(i, 0).sub(u.read(i, 0)));
+
+    match variables.basis(cl, um, up) {
+        Some(basis) => {
+            let a = linalg::matmul(basis.left.as_ref(), backward.as_ref());
+            let b = linalg::matmul(basis.left.as_ref(), forward.as_ref());
+            let slope = Mat::from_fn(m, 1, |i, _| limiter.slope(a.read(i, 0), b.read(i, 0)));
+            linalg::matmul(basis.right.as_ref(), slope.as_ref())
+        }
+        None => Mat::from_fn(m, 1, |i, _| {
+            limiter.slope(backward.read(i, 0), forward.read(i, 0))
+        }),
+    }
+}
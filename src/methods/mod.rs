@@ -3,9 +3,25 @@ use std::rc::Rc;
 use faer_core::{zipped, MatMut, MatRef};
 use reborrow::*;
 
+mod central;
+mod characteristic;
+mod mol;
+mod muscl;
+mod well_balanced;
+mod weno;
+
+pub use central::*;
+pub use characteristic::*;
+pub use mol::*;
+pub use muscl::*;
+pub use well_balanced::*;
+pub use weno::*;
+
 use crate::{
+    fluxes::{self, NumericalFlux},
     method::{Buffers, Method},
     problem::ConservationLaw,
+    riemann::RiemannSolver,
     Ctx, SimpleFloat,
 };
 
@@ -24,7 +40,7 @@ impl<F: SimpleFloat> Method<F> for UpwindLeft<F> {
     }
 
     fn init(&mut self, ctx: Ctx<F>) {
-        self.buf.resize(ctx.mesh.space.steps + 1);
+        self.buf.resize((ctx.mesh.space.steps + 1) * ctx.system_size);
     }
 
     fn apply<'m, 'pb>(
@@ -39,7 +55,7 @@ impl<F: SimpleFloat> Method<F> for UpwindLeft<F> {
         flux.bulk_flux_function(u.rb(), self.buf.get_mut(1));
 
         // component-wise schema
-        let r = ctx.mesh.time.delta.div(ctx.mesh.space.delta);
+        let r = ctx.dt.div(ctx.mesh.space.delta);
         let schema = |u: F, fum: F, fu: F| u.sub(r.mul(fu.sub(fum)));
 
         // apply
@@ -59,15 +75,15 @@ pub struct UpwindRight<F: SimpleFloat> {
 
 impl<F: SimpleFloat> Method<F> for UpwindRight<F> {
     fn left_ghost_cells(&self) -> usize {
-        1
+        0
     }
 
     fn right_ghost_cells(&self) -> usize {
-        0
+        1
     }
 
     fn init(&mut self, ctx: Ctx<F>) {
-        self.buf.resize(ctx.mesh.space.steps + 1);
+        self.buf.resize((ctx.mesh.space.steps + 1) * ctx.system_size);
     }
 
     fn apply<'m, 'pb>(
@@ -82,7 +98,7 @@ impl<F: SimpleFloat> Method<F> for UpwindRight<F> {
         flux.bulk_flux_function(ctx.right(), self.buf.get_mut(1));
 
         // component-wise schema
-        let r = ctx.mesh.time.delta.div(ctx.mesh.space.delta);
+        let r = ctx.dt.div(ctx.mesh.space.delta);
         let schema = |u: F, fu: F, fup: F| u.sub(r.mul(fup.sub(fu)));
 
         // apply
@@ -110,7 +126,7 @@ impl<F: SimpleFloat> Method<F> for LaxFriedrichs<F> {
     }
 
     fn init(&mut self, ctx: Ctx<F>) {
-        self.buf.resize(ctx.mesh.space.steps + 1);
+        self.buf.resize((ctx.mesh.space.steps + 1) * ctx.system_size);
     }
 
     fn apply<'m, 'pb>(
@@ -125,7 +141,7 @@ impl<F: SimpleFloat> Method<F> for LaxFriedrichs<F> {
         flux.bulk_flux_function(ctx.right(), self.buf.get_mut(1));
 
         // component-wise schema
-        let r = ctx.mesh.time.delta.div(ctx.mesh.space.delta);
+        let r = ctx.dt.div(ctx.mesh.space.delta);
         let schema = |um: F, up: F, fum: F, fup: F| -> F {
             (um.add(up).sub(r.mul(fup.sub(fum)))).mul(F::from_f64(0.5))
         };
@@ -157,8 +173,8 @@ impl<F: SimpleFloat> Method<F> for MacCormack<F> {
     }
 
     fn init(&mut self, ctx: Ctx<F>) {
-        self.buf_a.resize(ctx.mesh.space.steps + 1);
-        self.buf_b.resize(ctx.mesh.space.steps + 1);
+        self.buf_a.resize((ctx.mesh.space.steps + 1) * ctx.system_size);
+        self.buf_b.resize((ctx.mesh.space.steps + 1) * ctx.system_size);
     }
 
     fn apply<'m, 'pb>(
@@ -174,7 +190,7 @@ impl<F: SimpleFloat> Method<F> for MacCormack<F> {
         flux.bulk_flux_function(ctx.right(), self.buf_a.get_mut(2));
 
         // component-wise schema
-        let r = ctx.mesh.time.delta.div(ctx.mesh.space.delta);
+        let r = ctx.dt.div(ctx.mesh.space.delta);
         let forward = |u: F, fu: F, fup: F| -> F { u.sub(r.mul(fup.sub(fu))) };
 
         zipped!(
@@ -217,3 +233,186 @@ impl<F: SimpleFloat> Method<F> for MacCormack<F> {
         "MacCormarck"
     }
 }
+
+/// Two-step Lax-Wendroff scheme (Richtmyer formulation)
+#[derive(Default)]
+pub struct LaxWendroff<F: SimpleFloat> {
+    buf_a: Buffers<F, 3>,
+    buf_b: Buffers<F, 2>,
+}
+
+impl<F: SimpleFloat> Method<F> for LaxWendroff<F> {
+    fn left_ghost_cells(&self) -> usize {
+        1
+    }
+
+    fn right_ghost_cells(&self) -> usize {
+        1
+    }
+
+    fn init(&mut self, ctx: Ctx<F>) {
+        self.buf_a.resize((ctx.mesh.space.steps + 1) * ctx.system_size);
+        self.buf_b.resize((ctx.mesh.space.steps + 1) * ctx.system_size);
+    }
+
+    fn apply<'m, 'pb>(
+        &'m mut self,
+        ctx: Ctx<F>,
+        flux: Rc<dyn ConservationLaw<F> + 'pb>,
+        u: MatRef<F>,
+        v: MatMut<F>,
+    ) {
+        // stores fluxes
+        flux.bulk_flux_function(ctx.left(), self.buf_a.get_mut(0));
+        flux.bulk_flux_function(u.rb(), self.buf_a.get_mut(1));
+        flux.bulk_flux_function(ctx.right(), self.buf_a.get_mut(2));
+
+        // component-wise schema
+        let r = ctx.dt.div(ctx.mesh.space.delta);
+        let half = F::from_f64(0.5);
+        let midpoint =
+            |u: F, up: F, fu: F, fup: F| -> F { (u.add(up).sub(r.mul(fup.sub(fu)))).mul(half) };
+
+        // half step at the interfaces i-1/2 and i+1/2
+        zipped!(
+            self.buf_b.get_mut(0),
+            ctx.left(),
+            u.rb(),
+            self.buf_a.get(0),
+            self.buf_a.get(1)
+        )
+        .for_each(|mut v, um, u, fum, fu| {
+            v.write(midpoint(um.read(), u.read(), fum.read(), fu.read()))
+        });
+
+        zipped!(
+            self.buf_b.get_mut(1),
+            u.rb(),
+            ctx.right(),
+            self.buf_a.get(1),
+            self.buf_a.get(2)
+        )
+        .for_each(|mut v, u, up, fu, fup| {
+            v.write(midpoint(u.read(), up.read(), fu.read(), fup.read()))
+        });
+
+        flux.bulk_flux_function(self.buf_b.get(0), self.buf_a.get_mut(0));
+        flux.bulk_flux_function(self.buf_b.get(1), self.buf_a.get_mut(1));
+
+        let schema = |u: F, fhm: F, fhp: F| u.sub(r.mul(fhp.sub(fhm)));
+
+        // apply
+        zipped!(v, u, self.buf_a.get(0), self.buf_a.get(1))
+            .for_each(|mut v, u, fhm, fhp| v.write(schema(u.read(), fhm.read(), fhp.read())))
+    }
+
+    fn name(&self) -> &'static str {
+        "Lax-Wendroff (Richtmyer)"
+    }
+}
+
+/// Conservative finite-volume scheme `u - Δt/Δx (F_{i+1/2} - F_{i-1/2})` with interface fluxes
+/// given by the numerical flux `NF`
+#[derive(Default)]
+pub struct FiniteVolume<F: SimpleFloat, NF> {
+    flux: NF,
+    buf: Buffers<F, 1>,
+}
+
+impl<F: SimpleFloat, NF: NumericalFlux<F>> FiniteVolume<F, NF> {
+    pub fn new(flux: NF) -> Self {
+        Self {
+            flux,
+            buf: Buffers::default(),
+        }
+    }
+}
+
+impl<F: SimpleFloat, NF: NumericalFlux<F>> Method<F> for FiniteVolume<F, NF> {
+    fn left_ghost_cells(&self) -> usize {
+        1
+    }
+
+    fn right_ghost_cells(&self) -> usize {
+        1
+    }
+
+    fn init(&mut self, ctx: Ctx<F>) {
+        self.buf.resize((ctx.mesh.space.steps + 2) * ctx.system_size);
+    }
+
+    fn apply<'m, 'pb>(
+        &'m mut self,
+        ctx: Ctx<F>,
+        flux: Rc<dyn ConservationLaw<F> + 'pb>,
+        u: MatRef<F>,
+        v: MatMut<F>,
+    ) {
+        let (m, n) = (ctx.system_size, u.nrows());
+
+        // stores the interface fluxes, from the left face of the first cell to the right face of
+        // the last one
+        let lefts = ctx.left().into_row_chunks(m).chain([u.subrows(n - m, m)]);
+        let rights = u.into_row_chunks(m).chain([ctx.right().subrows(n - m, m)]);
+        for (j, (ul, ur)) in lefts.zip(rights).enumerate() {
+            let f = self.buf.get_mut(0).subrows(j * m, m);
+            self.flux.flux(flux.as_ref(), ul, ur, f);
+        }
+
+        // component-wise schema
+        let r = ctx.dt.div(ctx.mesh.space.delta);
+        let schema = |u: F, fm: F, fp: F| u.sub(r.mul(fp.sub(fm)));
+
+        // apply
+        let interfaces = self.buf.get(0);
+        zipped!(v, u, interfaces.subrows(0, n), interfaces.subrows(m, n))
+            .for_each(|mut v, u, fm, fp| v.write(schema(u.read(), fm.read(), fp.read())))
+    }
+
+    fn name(&self) -> &'static str {
+        self.flux.name()
+    }
+}
+
+/// First-order Godunov finite-volume scheme, with interface fluxes given by the Riemann solver
+/// `RS`: the [`FiniteVolume`] scheme of the [`fluxes::Godunov`] flux
+#[derive(Default)]
+pub struct Godunov<F: SimpleFloat, RS> {
+    scheme: FiniteVolume<F, fluxes::Godunov<RS>>,
+}
+
+impl<F: SimpleFloat, RS: RiemannSolver<F>> Godunov<F, RS> {
+    pub fn new(solver: RS) -> Self {
+        Self {
+            scheme: FiniteVolume::new(fluxes::Godunov(solver)),
+        }
+    }
+}
+
+impl<F: SimpleFloat, RS: RiemannSolver<F>> Method<F> for Godunov<F, RS> {
+    fn left_ghost_cells(&self) -> usize {
+        self.scheme.left_ghost_cells()
+    }
+
+    fn right_ghost_cells(&self) -> usize {
+        self.scheme.right_ghost_cells()
+    }
+
+    fn init(&mut self, ctx: Ctx<F>) {
+        self.scheme.init(ctx)
+    }
+
+    fn apply<'m, 'pb>(
+        &'m mut self,
+        ctx: Ctx<F>,
+        flux: Rc<dyn ConservationLaw<F> + 'pb>,
+        u: MatRef<F>,
+        v: MatMut<F>,
+    ) {
+        self.scheme.apply(ctx, flux, u, v)
+    }
+
+    fn name(&self) -> &'static str {
+        "Godunov"
+    }
+}
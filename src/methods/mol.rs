@@ -0,0 +1,180 @@
+use std::rc::Rc;
+
+use faer_core::{zipped, Mat, MatMut, MatRef};
+use reborrow::*;
+
+use crate::{
+    method::{Buffers, Method, SpatialOperator},
+    problem::ConservationLaw,
+    Ctx, SimpleFloat, Splitting,
+};
+
+/// Explicit Runge-Kutta time integrator of a [`MethodOfLines`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Integrator {
+    ForwardEuler,
+    /// Two-stage second-order strong stability preserving scheme (Heun)
+    SspRk2,
+    /// Three-stage third-order strong stability preserving scheme of Shu and Osher
+    #[default]
+    SspRk3,
+    /// Classical four-stage fourth-order scheme
+    Rk4,
+}
+
+impl Integrator {
+    /// Butcher tableau `(a, b, c)`: stage `i` evaluates `L` at `u + Δt Σ a_ij k_j` at time
+    /// `t + c_i Δt`, and the step is `u + Δt Σ b_i k_i`
+    fn tableau(self) -> (&'static [&'static [f64]], &'static [f64], &'static [f64]) {
+        match self {
+            Integrator::ForwardEuler => (&[&[]], &[1.], &[0.]),
+            Integrator::SspRk2 => (&[&[], &[1.]], &[0.5, 0.5], &[0., 1.]),
+            Integrator::SspRk3 => (
+                &[&[], &[1.], &[0.25, 0.25]],
+                &[1. / 6., 1. / 6., 2. / 3.],
+                &[0., 1., 0.5],
+            ),
+            Integrator::Rk4 => (
+                &[&[], &[0.5], &[0., 0.5], &[0., 0., 1.]],
+                &[1. / 6., 1. / 3., 1. / 3., 1. / 6.],
+                &[0., 0.5, 0.5, 1.],
+            ),
+        }
+    }
+}
+
+/// Integrates in time the spatial operator `S` with a Runge-Kutta [`Integrator`], filling the
+/// ghost cells of each intermediate stage with the problem's boundary condition
+#[derive(Default)]
+pub struct MethodOfLines<F: SimpleFloat, S> {
+    operator: S,
+    integrator: Integrator,
+    // intermediate stage, ghost cells included
+    stage: Buffers<F, 1>,
+    // time derivatives of the stages
+    k: Buffers<F, 4>,
+}
+
+impl<F: SimpleFloat, S: SpatialOperator<F>> MethodOfLines<F, S> {
+    pub fn new(operator: S, integrator: Integrator) -> Self {
+        Self {
+            operator,
+            integrator,
+            stage: Buffers::default(),
+            k: Buffers::default(),
+        }
+    }
+
+    pub fn with_integrator(self, integrator: Integrator) -> Self {
+        Self { integrator, ..self }
+    }
+}
+
+impl<F: SimpleFloat, S: SpatialOperator<F>> Method<F> for MethodOfLines<F, S> {
+    fn left_ghost_cells(&self) -> usize {
+        self.operator.left_ghost_cells()
+    }
+
+    fn right_ghost_cells(&self) -> usize {
+        self.operator.right_ghost_cells()
+    }
+
+    fn init(&mut self, ctx: Ctx<F>) {
+        let center = (ctx.mesh.space.steps + 1) * ctx.system_size;
+        let ghosts = (ctx.left_ghost_cells + ctx.right_ghost_cells) * ctx.system_size;
+        self.stage.resize(center + ghosts);
+        self.k.resize(center);
+        self.operator.init(ctx);
+    }
+
+    fn apply<'m, 'pb>(
+        &'m mut self,
+        ctx: Ctx<F>,
+        flux: Rc<dyn ConservationLaw<F> + 'pb>,
+        u: MatRef<F>,
+        mut v: MatMut<F>,
+    ) {
+        let Self {
+            operator,
+            integrator,
+            stage,
+            k,
+        } = self;
+        let (a, b, c) = integrator.tableau();
+        let dt = ctx.dt;
+        let t = ctx.t.sub(dt);
+        let offset = ctx.left_ghost_cells * ctx.system_size;
+
+        // combination u + Δt Σ w_j k_j
+        let combine = |k: &Buffers<F, 4>, w: &[f64], i: usize| {
+            w.iter().enumerate().fold(u.read(i, 0), |acc, (j, &w)| {
+                acc.add(dt.mul(F::from_f64(w)).mul(k.get(j).read(i, 0)))
+            })
+        };
+
+        // the operator sees the time of each stage
+        let stage_time = |c: f64| t.add(dt.mul(F::from_f64(c)));
+
+        // the first stage is the current solution, whose ghost cells are already filled
+        let first_ctx = Ctx {
+            t: stage_time(c[0]),
+            ..ctx
+        };
+        operator.rhs(first_ctx, flux.as_ref(), k.get_mut(0));
+        add_source(first_ctx, u, k.get_mut(0));
+        for (s, (a, &c)) in a.iter().zip(c).enumerate().skip(1) {
+            let mut w = stage.get_mut(0);
+            for i in 0..u.nrows() {
+                w.write(offset + i, 0, combine(k, a, i));
+            }
+            ctx.fill_ghost_cells(stage_time(c), w.rb_mut());
+
+            let stage_ctx = Ctx {
+                t: stage_time(c),
+                ..ctx.with_solution(stage.get(0))
+            };
+            operator.rhs(stage_ctx, flux.as_ref(), k.get_mut(s));
+            add_source(
+                stage_ctx,
+                stage.get(0).subrows(offset, u.nrows()),
+                k.get_mut(s),
+            );
+        }
+
+        for i in 0..u.nrows() {
+            v.write(i, 0, combine(k, b, i));
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        self.operator.name()
+    }
+
+    fn cfl_limit(&self) -> F {
+        self.operator.cfl_limit()
+    }
+
+    fn integrates_source(&self) -> bool {
+        true
+    }
+}
+
+/// Adds to the time derivative `k` of a stage the source `s(u, x, t)` of its cells `u`, at the
+/// time of the stage, unless there is no source or it is split from the method
+fn add_source<F: SimpleFloat>(ctx: Ctx<F>, u: MatRef<F>, k: MatMut<F>) {
+    let (Some(source), Splitting::Unsplit) = (&ctx.problem.source, ctx.splitting) else {
+        return;
+    };
+    let m = ctx.system_size;
+    let mut s = Mat::<F>::zeros(m, 1);
+    for ((x, u), mut k) in ctx
+        .mesh
+        .space
+        .iter()
+        .zip(u.into_row_chunks(m))
+        .zip(k.into_row_chunks(m))
+    {
+        source(u, x, ctx.t, s.as_mut());
+        zipped!(k.rb_mut(), s.as_ref()).for_each(|mut k, s| k.write(k.read().add(s.read())));
+    }
+}
@@ -0,0 +1,194 @@
+use std::rc::Rc;
+
+use faer_core::{zipped, MatMut, MatRef};
+
+use super::{characteristic::characteristic_slope, Variables};
+use crate::{
+    fluxes::{self, NumericalFlux},
+    linalg,
+    method::{Buffers, Method},
+    problem::ConservationLaw,
+    Ctx, SimpleFloat,
+};
+
+/// Slope limiter, taking the backward and forward differences `a` and `b` of a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Limiter {
+    #[default]
+    Minmod,
+    Superbee,
+    VanLeer,
+    /// Monotonized central
+    MC,
+    VanAlbada,
+}
+
+impl Limiter {
+    /// Limited slope of a cell, zero at extrema
+    pub fn slope<F: SimpleFloat>(self, a: F, b: F) -> F {
+        if a.mul(b) <= F::zero() {
+            return F::zero();
+        }
+
+        let two = F::from_f64(2.);
+        let sign = if a > F::zero() {
+            F::one()
+        } else {
+            F::one().neg()
+        };
+        let (abs_a, abs_b) = (linalg::abs(a), linalg::abs(b));
+        let min = |x: F, y: F| if x < y { x } else { y };
+        let max = |x: F, y: F| if x > y { x } else { y };
+
+        match self {
+            Limiter::Minmod => sign.mul(min(abs_a, abs_b)),
+            Limiter::Superbee => {
+                sign.mul(max(min(two.mul(abs_a), abs_b), min(abs_a, two.mul(abs_b))))
+            }
+            Limiter::VanLeer => two.mul(a).mul(b).div(a.add(b)),
+            Limiter::MC => sign.mul(min(
+                min(two.mul(abs_a), two.mul(abs_b)),
+                abs_a.add(abs_b).div(two),
+            )),
+            Limiter::VanAlbada => a.mul(b).mul(a.add(b)).div(a.mul(a).add(b.mul(b))),
+        }
+    }
+}
+
+/// Second-order MUSCL-Hancock scheme: limited piecewise linear reconstruction, evolved by half a
+/// time step before computing interface fluxes with the numerical flux `NF`
+#[derive(Default)]
+pub struct MusclHancock<F: SimpleFloat, NF> {
+    flux: NF,
+    limiter: Limiter,
+    variables: Variables,
+    // left and right faces of the cells and of the ghost cell on each side
+    faces: Buffers<F, 2>,
+    fluxes: Buffers<F, 2>,
+}
+
+impl<F: SimpleFloat, NF: NumericalFlux<F>> MusclHancock<F, NF> {
+    pub fn new(flux: NF, limiter: Limiter) -> Self {
+        Self {
+            flux,
+            limiter,
+            variables: Variables::default(),
+            faces: Buffers::default(),
+            fluxes: Buffers::default(),
+        }
+    }
+
+    pub fn with_limiter(self, limiter: Limiter) -> Self {
+        Self { limiter, ..self }
+    }
+
+    pub fn with_variables(self, variables: Variables) -> Self {
+        Self { variables, ..self }
+    }
+
+    /// Computes into faces 0 and 1 the values at the left and right faces of the cells, from
+    /// the one left of the domain to the one right of it, evolved by half a time step
+    fn evolved_faces(&mut self, ctx: Ctx<F>, flux: &dyn ConservationLaw<F>) {
+        let m = ctx.system_size;
+        let (first, len) = ((ctx.left_ghost_cells - 2) * m, (ctx.mesh.space.steps + 3) * m);
+        let [um, u, up] = [0, 1, 2].map(|k| ctx.u.subrows(first + k * m, len));
+        let (left, right) = (0, 1);
+        let half = F::from_f64(0.5);
+        let limiter = self.limiter;
+
+        match self.variables {
+            Variables::Conserved => {
+                zipped!(self.faces.get_mut(left), um, u, up).for_each(|mut v, um, u, up| {
+                    let (um, u, up) = (um.read(), u.read(), up.read());
+                    v.write(u.sub(limiter.slope(u.sub(um), up.sub(u)).mul(half)))
+                });
+                zipped!(self.faces.get_mut(right), um, u, up).for_each(|mut v, um, u, up| {
+                    let (um, u, up) = (um.read(), u.read(), up.read());
+                    v.write(u.add(limiter.slope(u.sub(um), up.sub(u)).mul(half)))
+                });
+            }
+            variables @ Variables::Characteristic { .. } => {
+                for j in 0..u.nrows() / m {
+                    let (um, u) = (um.subrows(j * m, m), u.subrows(j * m, m));
+                    let slope =
+                        characteristic_slope(variables, limiter, flux, um, u, up.subrows(j * m, m));
+                    for i in 0..m {
+                        let (u, delta) = (u.read(i, 0), slope.read(i, 0).mul(half));
+                        self.faces.get_mut(left).write(j * m + i, 0, u.sub(delta));
+                        self.faces.get_mut(right).write(j * m + i, 0, u.add(delta));
+                    }
+                }
+            }
+        }
+
+        flux.bulk_flux_function(self.faces.get(left), self.fluxes.get_mut(0));
+        flux.bulk_flux_function(self.faces.get(right), self.fluxes.get_mut(1));
+
+        let r = ctx.dt.div(ctx.mesh.space.delta).mul(half);
+        for face in [left, right] {
+            zipped!(
+                self.faces.get_mut(face),
+                self.fluxes.get(0),
+                self.fluxes.get(1)
+            )
+            .for_each(|mut v, fl, fr| v.write(v.read().add(r.mul(fl.read().sub(fr.read())))));
+        }
+    }
+}
+
+impl<F: SimpleFloat, NF: NumericalFlux<F>> Method<F> for MusclHancock<F, NF> {
+    fn left_ghost_cells(&self) -> usize {
+        2
+    }
+
+    fn right_ghost_cells(&self) -> usize {
+        2
+    }
+
+    fn init(&mut self, ctx: Ctx<F>) {
+        self.faces
+            .resize((ctx.mesh.space.steps + 3) * ctx.system_size);
+        self.fluxes
+            .resize((ctx.mesh.space.steps + 3) * ctx.system_size);
+    }
+
+    fn apply<'m, 'pb>(
+        &'m mut self,
+        ctx: Ctx<F>,
+        flux: Rc<dyn ConservationLaw<F> + 'pb>,
+        u: MatRef<F>,
+        v: MatMut<F>,
+    ) {
+        let (m, n) = (ctx.system_size, u.nrows());
+        self.evolved_faces(ctx, flux.as_ref());
+
+        // stores the interface fluxes, between the right face of a cell and the left face of the
+        // next one
+        let lefts = self.faces.get(1).subrows(0, n + m).into_row_chunks(m);
+        let rights = self.faces.get(0).subrows(m, n + m).into_row_chunks(m);
+        for (j, (ul, ur)) in lefts.zip(rights).enumerate() {
+            let f = self.fluxes.get_mut(0).subrows(j * m, m);
+            self.flux.flux(flux.as_ref(), ul, ur, f);
+        }
+
+        // component-wise schema
+        let r = ctx.dt.div(ctx.mesh.space.delta);
+        let schema = |u: F, fm: F, fp: F| u.sub(r.mul(fp.sub(fm)));
+
+        // apply
+        let interfaces = self.fluxes.get(0);
+        zipped!(v, u, interfaces.subrows(0, n), interfaces.subrows(m, n))
+            .for_each(|mut v, u, fm, fp| v.write(schema(u.read(), fm.read(), fp.read())))
+    }
+
+    fn name(&self) -> &'static str {
+        "MUSCL-Hancock"
+    }
+
+    fn cfl_limit(&self) -> F {
+        F::from_f64(0.5)
+    }
+}
+
+/// MUSCL-Hancock scheme with the Rusanov flux
+pub type Muscl<F> = MusclHancock<F, fluxes::Rusanov>;
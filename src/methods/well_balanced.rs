@@ -0,0 +1,207 @@
+use std::rc::Rc;
+
+use faer_core::{Mat, MatMut, MatRef};
+use reborrow::*;
+
+use crate::{
+    cl::ShallowWater, fluxes::NumericalFlux, linalg, method::Method, problem::ConservationLaw, Ctx,
+    SimpleFloat,
+};
+
+/// First-order well-balanced scheme of Audusse et al. for the [`ShallowWater`] equations over the
+/// bathymetry `b(x)`
+///
+/// The depths are reconstructed at each interface as `h* = max(0, h + b - max(b_L, b_R))`, so
+/// that the free surface `h + b` of a lake at rest is preserved exactly, fluxes are computed by
+/// `NF` on the reconstructed states and corrected by the hydrostatic pressure difference
+/// `g(h² - h*²)/2`, which discretises the source `-g h b'(x)` consistently with them.
+///
+/// With a positive numerical flux such as [`Hll`](crate::fluxes::Hll) or
+/// [`Rusanov`](crate::fluxes::Rusanov), the depth stays non-negative under the CFL condition
+/// `Δt/Δx · max(|u| + √(gh)) ≤ 1`. Beyond it, the fluxes leaving a cell are scaled down so that
+/// they do not drain more water than the cell holds, which keeps the depth non-negative without
+/// creating mass. Cells drying out are brought to rest.
+///
+/// The fluxes are those of the problem's law, which must be shallow-water equations of the same
+/// gravity. The bathymetry source is part of the scheme, the problem must not carry one of its
+/// own.
+pub struct HydrostaticReconstruction<F: SimpleFloat, NF, B> {
+    shallow_water: ShallowWater<F>,
+    flux: NF,
+    bathymetry: B,
+    // bathymetry of the cells, ghost cells included
+    b: Vec<F>,
+}
+
+impl<F, NF, B> HydrostaticReconstruction<F, NF, B>
+where
+    F: SimpleFloat,
+    NF: NumericalFlux<F>,
+    B: Fn(F) -> F,
+{
+    pub fn new(shallow_water: ShallowWater<F>, flux: NF, bathymetry: B) -> Self {
+        Self {
+            shallow_water,
+            flux,
+            bathymetry,
+            b: Vec::new(),
+        }
+    }
+
+    /// Panics unless `law` has the hydrostatic pressure `gh²/2` of the scheme's gravity
+    fn check_law(&self, law: &dyn ConservationLaw<F>) {
+        assert_eq!(
+            law.system_size(),
+            2,
+            "the hydrostatic reconstruction needs the shallow-water equations"
+        );
+        let u = Mat::from_fn(2, 1, |i, _| if i == 0 { F::one() } else { F::zero() });
+        let mut f = Mat::<F>::zeros(2, 1);
+        law.flux_function(u.as_ref(), f.as_mut());
+        let (g, gravity) = (f.read(1, 0).add(f.read(1, 0)), self.shallow_water.gravity());
+        assert!(
+            linalg::abs(g.sub(gravity)) <= linalg::abs(gravity).mul(F::from_f64(1e-12)),
+            "the gravity of the law differs from the one of the hydrostatic reconstruction"
+        );
+    }
+
+    /// State `u` over the bathymetry `b`, reconstructed at an interface of bathymetry `b_star`
+    fn reconstruct(&self, u: MatRef<F>, b: F, b_star: F) -> Mat<F> {
+        let h = u.read(0, 0).add(b).sub(b_star);
+        let h = if h > F::zero() { h } else { F::zero() };
+        let velocity = self.shallow_water.velocity(u);
+        Mat::from_fn(2, 1, |i, _| if i == 0 { h } else { h.mul(velocity) })
+    }
+
+    /// Flux of `law` through the interface between the cells `left` and `right`, and the depths
+    /// of both reconstructed states
+    fn interface_flux(
+        &self,
+        law: &dyn ConservationLaw<F>,
+        left: MatRef<F>,
+        right: MatRef<F>,
+        b_left: F,
+        b_right: F,
+        flux: MatMut<F>,
+    ) -> (F, F) {
+        let b_star = if b_left > b_right { b_left } else { b_right };
+        let left = self.reconstruct(left, b_left, b_star);
+        let right = self.reconstruct(right, b_right, b_star);
+        self.flux.flux(law, left.as_ref(), right.as_ref(), flux);
+        (left.read(0, 0), right.read(0, 0))
+    }
+}
+
+impl<F, NF, B> Method<F> for HydrostaticReconstruction<F, NF, B>
+where
+    F: SimpleFloat,
+    NF: NumericalFlux<F>,
+    B: Fn(F) -> F,
+{
+    fn left_ghost_cells(&self) -> usize {
+        1
+    }
+
+    fn right_ghost_cells(&self) -> usize {
+        1
+    }
+
+    fn init(&mut self, ctx: Ctx<F>) {
+        let space = ctx.mesh.space;
+        let cells = space.steps + 1 + ctx.left_ghost_cells + ctx.right_ghost_cells;
+        let first = space
+            .lower
+            .sub(space.delta.mul(F::from_f64(ctx.left_ghost_cells as f64)));
+        self.b = (0..cells)
+            .map(|k| (self.bathymetry)(first.add(space.delta.mul(F::from_f64(k as f64)))))
+            .collect();
+    }
+
+    fn apply<'m, 'pb>(
+        &'m mut self,
+        ctx: Ctx<F>,
+        flux: Rc<dyn ConservationLaw<F> + 'pb>,
+        u: MatRef<F>,
+        mut v: MatMut<F>,
+    ) {
+        self.check_law(&*flux);
+        let r = ctx.dt.div(ctx.mesh.space.delta);
+        let half_g = self.shallow_water.gravity().mul(F::from_f64(0.5));
+        let cells = u.nrows() / 2;
+
+        // fluxes through the interfaces i-1/2, from the left ghost cell to the right one, and the
+        // reconstructed depths on both of their sides
+        let mut fluxes = Mat::<F>::zeros(2, cells + 1);
+        let mut depths = Vec::with_capacity(cells + 1);
+        for k in 0..=cells {
+            let left = if k == 0 {
+                ctx.left().subrows(0, 2)
+            } else {
+                u.subrows(2 * (k - 1), 2)
+            };
+            let right = if k == cells {
+                ctx.right().subrows(2 * (cells - 1), 2)
+            } else {
+                u.subrows(2 * k, 2)
+            };
+            let (b_left, b_right) = (self.b[k], self.b[k + 1]);
+            let f = fluxes.as_mut().col(k);
+            depths.push(self.interface_flux(&*flux, left, right, b_left, b_right, f));
+        }
+
+        // positivity: the fluxes leaving each cell are scaled down to drain at most its water
+        let positive = |f: F| if f > F::zero() { f } else { F::zero() };
+        let scales: Vec<F> = (0..cells)
+            .map(|j| {
+                let h = u.read(2 * j, 0);
+                let outflow = positive(fluxes.read(0, j + 1))
+                    .add(positive(fluxes.read(0, j).neg()))
+                    .mul(r);
+                if outflow > h {
+                    h.div(outflow)
+                } else {
+                    F::one()
+                }
+            })
+            .collect();
+        for k in 0..=cells {
+            let mass = fluxes.read(0, k);
+            let scale = if mass > F::zero() && k > 0 {
+                scales[k - 1]
+            } else if mass < F::zero() && k < cells {
+                scales[k]
+            } else {
+                F::one()
+            };
+            for i in 0..2 {
+                fluxes.write(i, k, fluxes.read(i, k).mul(scale));
+            }
+        }
+
+        for j in 0..cells {
+            let u = u.subrows(2 * j, 2);
+            let h = u.read(0, 0);
+
+            // fluxes at i-1/2 and i+1/2 seen from the cell, with the hydrostatic correction
+            let correction = |h_star: F| half_g.mul(h.mul(h).sub(h_star.mul(h_star)));
+            let (fm, fp) = (fluxes.as_ref().col(j), fluxes.as_ref().col(j + 1));
+            let (hm, hp) = (depths[j].1, depths[j + 1].0);
+            let mut v = v.rb_mut().subrows(j * 2, 2);
+            v.write(0, 0, h.sub(r.mul(fp.read(0, 0).sub(fm.read(0, 0)))));
+            let momentum = fp
+                .read(1, 0)
+                .add(correction(hp))
+                .sub(fm.read(1, 0).add(correction(hm)));
+            v.write(1, 0, u.read(1, 0).sub(r.mul(momentum)));
+
+            // drying cells are brought to rest
+            if self.shallow_water.is_dry(v.read(0, 0)) {
+                v.write(1, 0, F::zero());
+            }
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "hydrostatic reconstruction"
+    }
+}
@@ -0,0 +1,155 @@
+use faer_core::{zipped, Mat, MatMut, MatRef};
+use reborrow::*;
+
+use super::{MethodOfLines, Variables};
+use crate::{
+    linalg,
+    method::{Buffers, SpatialOperator},
+    problem::ConservationLaw,
+    Ctx, SimpleFloat,
+};
+
+/// Regularization of the smoothness indicators
+const WENO_EPSILON: f64 = 1e-6;
+
+/// Fifth-order WENO reconstruction at the right face of the central value of `v`, from the
+/// five-point stencil centered on it
+fn weno5<F: SimpleFloat>(v: [F; 5]) -> F {
+    let c = |x: f64| F::from_f64(x);
+    let sq = |x: F| x.mul(x);
+    let [a, b, u, d, e] = v;
+
+    // candidate stencils
+    let q0 = c(2.).mul(a).sub(c(7.).mul(b)).add(c(11.).mul(u)).div(c(6.));
+    let q1 = c(5.).mul(u).sub(b).add(c(2.).mul(d)).div(c(6.));
+    let q2 = c(2.).mul(u).add(c(5.).mul(d)).sub(e).div(c(6.));
+
+    // smoothness indicators
+    let k = c(13. / 12.);
+    let b0 = k
+        .mul(sq(a.sub(c(2.).mul(b)).add(u)))
+        .add(c(0.25).mul(sq(a.sub(c(4.).mul(b)).add(c(3.).mul(u)))));
+    let b1 = k
+        .mul(sq(b.sub(c(2.).mul(u)).add(d)))
+        .add(c(0.25).mul(sq(b.sub(d))));
+    let b2 = k
+        .mul(sq(u.sub(c(2.).mul(d)).add(e)))
+        .add(c(0.25).mul(sq(c(3.).mul(u).sub(c(4.).mul(d)).add(e))));
+
+    // nonlinear weights
+    let eps = c(WENO_EPSILON);
+    let w0 = c(0.1).div(sq(eps.add(b0)));
+    let w1 = c(0.6).div(sq(eps.add(b1)));
+    let w2 = c(0.3).div(sq(eps.add(b2)));
+
+    w0.mul(q0)
+        .add(w1.mul(q1))
+        .add(w2.mul(q2))
+        .div(w0.add(w1).add(w2))
+}
+
+/// Fifth-order WENO reconstruction of Jiang and Shu with global Lax-Friedrichs flux splitting
+/// `f± = (f(u) ± αu) / 2`, see [`Weno5`] for the full scheme
+#[derive(Default)]
+pub struct Weno5Operator<F: SimpleFloat> {
+    variables: Variables,
+    // split fluxes, ghost cells included
+    split: Buffers<F, 2>,
+    interfaces: Buffers<F, 2>,
+}
+
+impl<F: SimpleFloat> Weno5Operator<F> {
+    pub fn new(variables: Variables) -> Self {
+        Self {
+            variables,
+            split: Buffers::default(),
+            interfaces: Buffers::default(),
+        }
+    }
+
+    pub fn with_variables(self, variables: Variables) -> Self {
+        Self { variables, ..self }
+    }
+}
+
+impl<F: SimpleFloat> SpatialOperator<F> for Weno5Operator<F> {
+    fn left_ghost_cells(&self) -> usize {
+        3
+    }
+
+    fn right_ghost_cells(&self) -> usize {
+        3
+    }
+
+    fn init(&mut self, ctx: Ctx<F>) {
+        let center = (ctx.mesh.space.steps + 1) * ctx.system_size;
+        let ghosts = (ctx.left_ghost_cells + ctx.right_ghost_cells) * ctx.system_size;
+        self.split.resize(center + ghosts);
+        self.interfaces.resize(center);
+    }
+
+    fn rhs(&mut self, ctx: Ctx<F>, flux: &dyn ConservationLaw<F>, rhs: MatMut<F>) {
+        let m = ctx.system_size;
+        let half = F::from_f64(0.5);
+
+        let alpha = flux.bulk_max_wave_speed(ctx.u);
+        let [mut plus, mut minus] = self.split.get_all_mut();
+        flux.bulk_flux_function(ctx.u, minus.rb_mut());
+        zipped!(plus.rb_mut(), minus.rb_mut(), ctx.u).for_each(|mut p, mut q, u| {
+            let (f, au) = (q.read(), alpha.mul(u.read()));
+            p.write(f.add(au).mul(half));
+            q.write(f.sub(au).mul(half));
+        });
+
+        // stores interface fluxes at i-1/2 and i+1/2
+        let [mut fm, mut fp] = self.interfaces.get_all_mut();
+        for (q, mut interface) in [(-1, fm.rb_mut()), (0, fp.rb_mut())] {
+            let p = [-2, -1, 0, 1, 2].map(|p| ctx.slide_of(plus.rb(), q + p));
+            let n = [3, 2, 1, 0, -1].map(|p| ctx.slide_of(minus.rb(), q + p));
+            for j in 0..interface.nrows() / m {
+                let (ul, ur) = (ctx.slide(q), ctx.slide(q + 1));
+                let (ul, ur) = (ul.subrows(j * m, m), ur.subrows(j * m, m));
+                match self.variables.basis(flux, ul, ur) {
+                    Some(basis) => {
+                        // reconstructs the characteristic split fluxes at the interface
+                        let project =
+                            |x: MatRef<F>| linalg::matmul(basis.left.as_ref(), x.subrows(j * m, m));
+                        let (wp, wn) = (p.map(project), n.map(project));
+                        let w = Mat::from_fn(m, 1, |c, _| {
+                            let fp = weno5([0, 1, 2, 3, 4].map(|k| wp[k].read(c, 0)));
+                            let fm = weno5([0, 1, 2, 3, 4].map(|k| wn[k].read(c, 0)));
+                            fp.add(fm)
+                        });
+                        interface
+                            .rb_mut()
+                            .subrows(j * m, m)
+                            .clone_from(linalg::matmul(basis.right.as_ref(), w.as_ref()).as_ref());
+                    }
+                    None => {
+                        for i in j * m..(j + 1) * m {
+                            let f =
+                                weno5(p.map(|p| p.read(i, 0))).add(weno5(n.map(|n| n.read(i, 0))));
+                            interface.write(i, 0, f);
+                        }
+                    }
+                }
+            }
+        }
+
+        let dx = ctx.mesh.space.delta;
+        zipped!(rhs, fm.rb(), fp.rb())
+            .for_each(|mut v, fm, fp| v.write(fm.read().sub(fp.read()).div(dx)));
+    }
+
+    fn name(&self) -> &'static str {
+        "WENO5"
+    }
+
+    fn cfl_limit(&self) -> F {
+        F::from_f64(0.5)
+    }
+}
+
+/// Fifth-order WENO scheme of Jiang and Shu, with the third-order strong stability preserving
+/// Runge-Kutta time integrator by default
+pub type Weno5<F> = MethodOfLines<F, Weno5Operator<F>>;
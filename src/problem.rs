@@ -1,88 +1,120 @@
 use core::fmt;
 use std::rc::Rc;
 
-use faer_core::{MatMut, MatRef};
+use faer_core::{Mat, MatMut, MatRef};
 use reborrow::*;
 
-use crate::{Ctx, SimpleFloat};
+use crate::{linalg, Ctx, SimpleFloat};
+
+/// Eigen-decomposition `f'(u) = R diag(λ) L` of the flux Jacobian, with `L = R⁻¹`
+#[derive(Debug, Clone)]
+pub struct EigenDecomposition<F: SimpleFloat> {
+    /// Eigenvalues `λ` (wave speeds) in increasing order, as a column
+    pub values: Mat<F>,
+    /// Right eigenvectors `R`, one per column
+    pub right: Mat<F>,
+    /// Left eigenvectors `L`, one per row
+    pub left: Mat<F>,
+}
 
-/// A hyperbolic PDE of the form `u_t + (f(u))_x = 0`
+/// A hyperbolic PDE of the form `u_t + (f(u))_x = 0`, see [`Problem::with_source`] for source
+/// terms
 pub trait ConservationLaw<F: SimpleFloat> {
     fn system_size(&self) -> usize;
     fn flux_function(&self, u: MatRef<F>, v: MatMut<F>);
 
-    fn bulk_flux_function(&self, u: MatRef<F>, v: MatMut<F>) {
-        for (u, v) in u
-            .rb()
-            .into_row_chunks(self.system_size())
-            .zip(v.into_row_chunks(self.system_size()))
-        {
-            self.flux_function(u, v)
-        }
-    }
-}
-
-pub mod cl {
-    use super::*;
-    use faer_core::zipped;
-    use std::marker::PhantomData;
-
-    pub struct General<F, G> {
-        system_size: usize,
-        flux_function: G,
-        _marker: PhantomData<F>,
-    }
-
-    impl<F: SimpleFloat, G: Fn(MatRef<F>, MatMut<F>)> General<F, G> {
-        pub fn new(system_size: usize, flux_function: G) -> Self {
-            Self {
-                system_size,
-                flux_function,
-                _marker: PhantomData,
+    /// Flux Jacobian `f'(u)` into the `system_size × system_size` matrix `jac`, approximated by
+    /// central differences of `flux_function` by default
+    fn jacobian(&self, u: MatRef<F>, mut jac: MatMut<F>) {
+        let m = self.system_size();
+        let mut shifted = u.to_owned();
+        let mut plus = Mat::<F>::zeros(m, 1);
+        let mut minus = Mat::<F>::zeros(m, 1);
+
+        for j in 0..m {
+            let uj = u.read(j, 0);
+            let h = linalg::difference_step(uj);
+
+            shifted.write(j, 0, uj.add(h));
+            self.flux_function(shifted.as_ref(), plus.as_mut());
+            shifted.write(j, 0, uj.sub(h));
+            self.flux_function(shifted.as_ref(), minus.as_mut());
+            shifted.write(j, 0, uj);
+
+            for i in 0..m {
+                jac.write(i, j, plus.read(i, 0).sub(minus.read(i, 0)).div(h.add(h)));
             }
         }
     }
 
-    impl<F: SimpleFloat, G: Fn(MatRef<F>, MatMut<F>)> ConservationLaw<F> for General<F, G> {
-        #[inline]
-        fn system_size(&self) -> usize {
-            self.system_size
-        }
-
-        #[inline]
-        fn flux_function(&self, u: MatRef<F>, v: MatMut<F>) {
-            (self.flux_function)(u, v)
+    /// Eigenvalues of the flux Jacobian at the state `u` in increasing order, into the column
+    /// `values`
+    ///
+    /// By default, computed from [`jacobian`](Self::jacobian), and NaN where the eigenvalues are
+    /// not real (the system is not hyperbolic at `u`).
+    fn eigenvalues(&self, u: MatRef<F>, mut values: MatMut<F>) {
+        let m = self.system_size();
+        let mut jac = Mat::<F>::zeros(m, m);
+        self.jacobian(u, jac.as_mut());
+        match linalg::eigenvalues(jac.as_ref()) {
+            Some(lambda) => {
+                for (i, lambda) in lambda.into_iter().enumerate() {
+                    values.write(i, 0, lambda);
+                }
+            }
+            None => values.fill(F::nan()),
         }
     }
 
-    pub struct Scalar<F, G> {
-        flux_function: G,
-        _marker: PhantomData<F>,
+    /// Eigen-structure of the flux Jacobian at the state `u`, or `None` if it is not
+    /// diagonalizable with real eigenvalues
+    fn eigen_decomposition(&self, u: MatRef<F>) -> Option<EigenDecomposition<F>> {
+        let m = self.system_size();
+        let mut jac = Mat::<F>::zeros(m, m);
+        self.jacobian(u, jac.as_mut());
+        linalg::eigen_decomposition(jac.as_ref())
     }
 
-    impl<F: SimpleFloat, G: Fn(F) -> F> Scalar<F, G> {
-        pub fn new(flux_function: G) -> Self {
-            Self {
-                flux_function,
-                _marker: PhantomData,
+    /// Largest absolute wave speed `max |λ|` at the state `u`, NaN if one of the eigenvalues
+    /// is NaN
+    ///
+    /// The default goes through [`eigenvalues`](Self::eigenvalues), hence a Jacobian and an
+    /// eigenvalue decomposition per call, which adaptive time stepping and some numerical fluxes
+    /// pay for every cell at every step: laws with known wave speeds should override it, as the
+    /// ones of [`cl`](crate::cl) do.
+    fn max_wave_speed(&self, u: MatRef<F>) -> F {
+        let mut values = Mat::<F>::zeros(self.system_size(), 1);
+        self.eigenvalues(u, values.as_mut());
+        (0..values.nrows()).fold(F::zero(), |max, i| {
+            let speed = linalg::abs(values.read(i, 0));
+            if speed > max || speed.is_nan() {
+                speed
+            } else {
+                max
             }
-        }
+        })
     }
 
-    impl<F: SimpleFloat, G: Fn(F) -> F> ConservationLaw<F> for Scalar<F, G> {
-        #[inline]
-        fn system_size(&self) -> usize {
-            1
-        }
-
-        #[inline]
-        fn flux_function(&self, u: MatRef<F>, v: MatMut<F>) {
-            zipped!(v, u).for_each(|mut v, u| v.write((self.flux_function)(u.read())));
-        }
+    /// Largest absolute wave speed over a whole solution vector, NaN if one of them is NaN
+    fn bulk_max_wave_speed(&self, u: MatRef<F>) -> F {
+        u.into_row_chunks(self.system_size())
+            .fold(F::zero(), |max, u| {
+                let speed = self.max_wave_speed(u);
+                if speed > max || speed.is_nan() {
+                    speed
+                } else {
+                    max
+                }
+            })
+    }
 
-        #[inline]
-        fn bulk_flux_function(&self, u: MatRef<F>, v: MatMut<F>) {
-            zipped!(v, u).for_each(|mut v, u| v.write((self.flux_function)(u.read())));
+    fn bulk_flux_function(&self, u: MatRef<F>, v: MatMut<F>) {
+        for (u, v) in u
+            .rb()
+            .into_row_chunks(self.system_size())
+            .zip(v.into_row_chunks(self.system_size()))
+        {
+            self.flux_function(u, v)
         }
     }
 }
@@ -98,9 +130,29 @@ pub trait BoundaryCondition<F: SimpleFloat> {
     fn apply(&self, ctx: Ctx<F>, left: MatMut<F>, center: MatRef<F>, right: MatMut<F>);
 }
 
+/// End of the domain a ghost block is attached to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// A boundary condition acting on a single end of the domain, see [`bc::Split`](crate::bc::Split)
+/// to combine two of them
+pub trait OneSidedBoundaryCondition<F: SimpleFloat> {
+    /// `ghost` is the ghost block of `side`, ordered like the solution vector (the ghost cell
+    /// farthest from the domain comes first on the left and last on the right)
+    fn apply_side(&self, ctx: Ctx<F>, side: Side, ghost: MatMut<F>, center: MatRef<F>);
+}
+
 pub trait InitialCondition<F: SimpleFloat>: Fn(F, MatMut<F>) {}
 impl<F: SimpleFloat, T> InitialCondition<F> for T where T: Fn(F, MatMut<F>) {}
 
+/// Source `s(u, x, t)` of a balance law `u_t + (f(u))_x = s(u, x, t)`, written into its last
+/// argument for a single cell
+pub trait SourceTerm<F: SimpleFloat>: Fn(MatRef<F>, F, F, MatMut<F>) {}
+impl<F: SimpleFloat, T> SourceTerm<F> for T where T: Fn(MatRef<F>, F, F, MatMut<F>) {}
+
 #[derive(Clone)]
 pub struct Problem<'pb, F: SimpleFloat> {
     pub(crate) name: String,
@@ -108,6 +160,7 @@ pub struct Problem<'pb, F: SimpleFloat> {
     pub(crate) domain: Domain<F>,
     pub(crate) bc: Rc<dyn BoundaryCondition<F> + 'pb>,
     pub(crate) u0: Rc<dyn InitialCondition<F> + 'pb>,
+    pub(crate) source: Option<Rc<dyn SourceTerm<F> + 'pb>>,
 }
 
 impl<'pb, F: SimpleFloat> Problem<'pb, F> {
@@ -124,6 +177,16 @@ impl<'pb, F: SimpleFloat> Problem<'pb, F> {
             domain,
             bc: Rc::new(bc),
             u0: Rc::new(u0),
+            source: None,
+        }
+    }
+
+    /// Turns the problem into a balance law, see [`Splitting`](crate::Splitting) for how the
+    /// source is integrated
+    pub fn with_source(self, source: impl SourceTerm<F> + 'pb) -> Self {
+        Self {
+            source: Some(Rc::new(source)),
+            ..self
         }
     }
 
@@ -140,6 +203,10 @@ impl<F: SimpleFloat> fmt::Debug for Problem<'_, F> {
             .field("domain", &self.domain)
             .field("bc", &"<dyn BoundaryCondition<_>>")
             .field("u0", &"<dyn InitialCondition<_>>")
+            .field(
+                "source",
+                &self.source.as_ref().map(|_| "<dyn SourceTerm<_>>"),
+            )
             .finish()
     }
 }
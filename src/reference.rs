@@ -0,0 +1,428 @@
+//! Exact solutions to verify numerical results against
+
+use std::{cmp::Ordering, rc::Rc};
+
+use crate::{
+    linalg,
+    riemann::{scalar_flux, scalar_speed},
+    ConservationLaw, Grid, SimpleFloat,
+};
+
+/// Intervals on which the primitive of smooth initial data is tabulated
+const PRIMITIVE_INTERVALS: usize = 4096;
+
+/// Values of the solution first scanned for roots of the characteristic equation, each interval
+/// being refined until the feet of the characteristics at its ends are one interval of the
+/// tabulated initial data apart
+const CHARACTERISTIC_SAMPLES: usize = 64;
+
+const CHARACTERISTIC_REFINEMENTS: usize = 24;
+
+const CHARACTERISTIC_BISECTIONS: usize = 60;
+
+/// Values of the solution at which the flux is checked to be strictly convex or concave
+const CONVEXITY_SAMPLES: usize = 64;
+
+/// Initial data `u0` along with a primitive `U0`
+enum InitialData<'a, F> {
+    /// `values[k]` between `breakpoints[k - 1]` and `breakpoints[k]`
+    PiecewiseConstant { breakpoints: Vec<F>, values: Vec<F> },
+    /// `u0` on `grid`, extended by its boundary values or periodically, with `U0` tabulated on
+    /// the nodes and interpolated by cubic Hermite polynomials
+    Smooth {
+        u0: Box<dyn Fn(F) -> F + 'a>,
+        grid: Grid<F>,
+        nodes: Vec<F>,
+        values: Vec<F>,
+        primitive: Vec<F>,
+        periodic: bool,
+    },
+}
+
+impl<'a, F: SimpleFloat> InitialData<'a, F> {
+    fn smooth(u0: impl Fn(F) -> F + 'a, domain: (F, F), periodic: bool) -> Self {
+        let grid = Grid::from_steps(domain.0, domain.1, PRIMITIVE_INTERVALS);
+        let nodes: Vec<F> = grid.iter().collect();
+        let values: Vec<F> = nodes.iter().map(|&y| u0(y)).collect();
+
+        // three-point Gauss-Legendre quadrature on each interval
+        let radius = grid.delta.mul(F::from_f64(0.5));
+        let offset = radius.mul(F::from_f64(0.6f64.sqrt()));
+        let (outer, center) = (F::from_f64(5. / 9.), F::from_f64(8. / 9.));
+        let mut primitive = vec![F::zero()];
+        for &y in &nodes[..grid.steps] {
+            let mid = y.add(radius);
+            let integral = outer
+                .mul(u0(mid.sub(offset)).add(u0(mid.add(offset))))
+                .add(center.mul(u0(mid)))
+                .mul(radius);
+            primitive.push(primitive[primitive.len() - 1].add(integral));
+        }
+
+        Self::Smooth {
+            u0: Box::new(u0),
+            grid,
+            nodes,
+            values,
+            primitive,
+            periodic,
+        }
+    }
+
+    /// Smallest and largest values of `u0`
+    fn range(&self) -> (F, F) {
+        let values = match self {
+            Self::PiecewiseConstant { values, .. } | Self::Smooth { values, .. } => values,
+        };
+        values.iter().fold((values[0], values[0]), |(lo, hi), &u| {
+            (if u < lo { u } else { lo }, if u > hi { u } else { hi })
+        })
+    }
+
+    /// Number of periods between the lower end of the domain and `y`, and `y` brought back into
+    /// the domain
+    fn wrap(grid: &Grid<F>, y: F) -> (F, F) {
+        let length = grid.upper.sub(grid.lower);
+        let (mut periods, mut y) = (F::zero(), y);
+        while y < grid.lower {
+            (periods, y) = (periods.sub(F::one()), y.add(length));
+        }
+        while y >= grid.upper {
+            (periods, y) = (periods.add(F::one()), y.sub(length));
+        }
+        (periods, y)
+    }
+
+    fn value(&self, y: F) -> F {
+        match self {
+            Self::PiecewiseConstant {
+                breakpoints,
+                values,
+            } => values[breakpoints.partition_point(|&b| b <= y)],
+            Self::Smooth {
+                u0,
+                grid,
+                values,
+                periodic,
+                ..
+            } => {
+                if *periodic {
+                    u0(Self::wrap(grid, y).1)
+                } else if y < grid.lower {
+                    values[0]
+                } else if y > grid.upper {
+                    values[grid.steps]
+                } else {
+                    u0(y)
+                }
+            }
+        }
+    }
+
+    fn primitive(&self, y: F) -> F {
+        match self {
+            Self::PiecewiseConstant {
+                breakpoints,
+                values,
+            } => {
+                // from the first breakpoint, each value contributing over the part of its
+                // interval below y
+                let Some(&origin) = breakpoints.first() else {
+                    return values[0].mul(y);
+                };
+                let below = |a: F, b: F| {
+                    let end = if y < b { y } else { b };
+                    if end > a {
+                        end.sub(a)
+                    } else {
+                        F::zero()
+                    }
+                };
+                let first = values[0].mul(if y < origin { y.sub(origin) } else { F::zero() });
+                values[1..]
+                    .iter()
+                    .enumerate()
+                    .fold(first, |sum, (k, &value)| {
+                        let upper = breakpoints.get(k + 1).copied().unwrap_or(y);
+                        sum.add(value.mul(below(breakpoints[k], upper)))
+                    })
+            }
+            Self::Smooth {
+                grid,
+                nodes,
+                values,
+                primitive,
+                periodic,
+                ..
+            } => {
+                let total = primitive[grid.steps];
+                let (periods, y) = if *periodic {
+                    Self::wrap(grid, y)
+                } else if y < grid.lower {
+                    return values[0].mul(y.sub(grid.lower));
+                } else if y > grid.upper {
+                    return total.add(values[grid.steps].mul(y.sub(grid.upper)));
+                } else {
+                    (F::zero(), y)
+                };
+
+                let k = nodes[1..grid.steps].partition_point(|&node| node <= y);
+                let s = y.sub(nodes[k]).div(grid.delta);
+                let (one, two, three) = (F::one(), F::from_f64(2.), F::from_f64(3.));
+                let (s2, s3) = (s.mul(s), s.mul(s).mul(s));
+                let h00 = two.mul(s3).sub(three.mul(s2)).add(one);
+                let h10 = s3.sub(two.mul(s2)).add(s);
+                let h01 = three.mul(s2).sub(two.mul(s3));
+                let h11 = s3.sub(s2);
+                periods.mul(total).add(
+                    h00.mul(primitive[k]).add(h01.mul(primitive[k + 1])).add(
+                        h10.mul(values[k])
+                            .add(h11.mul(values[k + 1]))
+                            .mul(grid.delta),
+                    ),
+                )
+            }
+        }
+    }
+}
+
+/// Exact entropy solution of a scalar conservation law with a strictly convex or concave flux
+/// `f`, such as [`cl::Scalar`](crate::cl::Scalar), from the Lax-Oleinik formula
+///
+/// `u(x, t)` minimises `U0(x - t f'(u)) + t (u f'(u) - f(u))` over the range of the initial data
+/// (maximises it for a concave flux), `U0` being a primitive of `u0`. The extrema of this
+/// function are the roots of the characteristic equation `u = u0(x - t f'(u))`, and comparing
+/// them places the shocks. For piecewise-constant data, these are the constant values and the
+/// values in the fans centred on the breakpoints, found exactly. For smooth data, they are located
+/// by bisection after a scan fine enough for the feet of the characteristics to be at most one
+/// tabulation interval of the data apart, so that no pair of roots closer than the data can
+/// resolve is missed.
+pub struct EntropySolution<'a, F: SimpleFloat> {
+    cl: Rc<dyn ConservationLaw<F> + 'a>,
+    data: InitialData<'a, F>,
+    range: (F, F),
+    concave: bool,
+}
+
+impl<'a, F: SimpleFloat> EntropySolution<'a, F> {
+    /// Checks that `cl` is scalar, and that its flux is strictly convex or concave over the range
+    /// of the data by sampling `f'`, which must be strictly monotone
+    fn new(cl: impl ConservationLaw<F> + 'a, data: InitialData<'a, F>) -> Self {
+        assert_eq!(
+            cl.system_size(),
+            1,
+            "entropy solutions need a scalar conservation law"
+        );
+
+        let (lo, hi) = data.range();
+        let speeds: Vec<F> = (0..=CONVEXITY_SAMPLES)
+            .map(|k| {
+                let s = F::from_f64(k as f64 / CONVEXITY_SAMPLES as f64);
+                scalar_speed(&cl, lo.add(hi.sub(lo).mul(s)))
+            })
+            .collect();
+        let concave = speeds[CONVEXITY_SAMPLES] < speeds[0];
+        assert!(
+            lo == hi
+                || speeds
+                    .windows(2)
+                    .all(|s| if concave { s[1] < s[0] } else { s[0] < s[1] }),
+            "the flux is neither strictly convex nor strictly concave over the range of the \
+             initial data"
+        );
+
+        Self {
+            cl: Rc::new(cl),
+            range: (lo, hi),
+            data,
+            concave,
+        }
+    }
+
+    /// Initial data equal to `values[k]` between `breakpoints[k - 1]` and `breakpoints[k]`, with
+    /// one more value than breakpoints, such as a Riemann problem
+    ///
+    /// # Panics
+    ///
+    /// Panics if the breakpoints are not sorted or their number does not match the values, if
+    /// `cl` is not a scalar conservation law, or if its flux is not strictly convex or concave
+    /// over the range of the values.
+    pub fn piecewise_constant(
+        cl: impl ConservationLaw<F> + 'a,
+        breakpoints: &[F],
+        values: &[F],
+    ) -> Self {
+        assert_eq!(
+            breakpoints.len() + 1,
+            values.len(),
+            "piecewise-constant data needs one more value than breakpoints"
+        );
+        assert!(
+            breakpoints.windows(2).all(|b| b[0] <= b[1]),
+            "breakpoints are not sorted"
+        );
+        Self::new(
+            cl,
+            InitialData::PiecewiseConstant {
+                breakpoints: breakpoints.to_vec(),
+                values: values.to_vec(),
+            },
+        )
+    }
+
+    /// Smooth initial data `u0` on `domain`, extended by its boundary values outside of it
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cl` is not a scalar conservation law, or if its flux is not strictly convex or
+    /// concave over the range of `u0`.
+    pub fn smooth(
+        cl: impl ConservationLaw<F> + 'a,
+        u0: impl Fn(F) -> F + 'a,
+        domain: (F, F),
+    ) -> Self {
+        Self::new(cl, InitialData::smooth(u0, domain, false))
+    }
+
+    /// Smooth initial data `u0` on `domain`, repeated periodically outside of it
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cl` is not a scalar conservation law, or if its flux is not strictly convex or
+    /// concave over the range of `u0`.
+    pub fn periodic(
+        cl: impl ConservationLaw<F> + 'a,
+        u0: impl Fn(F) -> F + 'a,
+        domain: (F, F),
+    ) -> Self {
+        Self::new(cl, InitialData::smooth(u0, domain, true))
+    }
+
+    /// Solution `u(x, t)`, `t` being measured from the initial data
+    pub fn eval(&self, x: F, t: F) -> F {
+        let (lo, hi) = self.range;
+        if lo == hi {
+            return lo;
+        }
+        if t <= F::zero() {
+            return self.data.value(x);
+        }
+
+        let cl = self.cl.as_ref();
+        let foot = |u: F| x.sub(t.mul(scalar_speed(cl, u)));
+        let objective = |u: F| {
+            let speed = scalar_speed(cl, u);
+            let g = self
+                .data
+                .primitive(x.sub(t.mul(speed)))
+                .add(t.mul(u.mul(speed).sub(scalar_flux(cl, u))));
+            if self.concave {
+                g.neg()
+            } else {
+                g
+            }
+        };
+
+        let candidates = match &self.data {
+            InitialData::PiecewiseConstant {
+                breakpoints,
+                values,
+            } => {
+                // the fan centred on each breakpoint b, where the foot of the characteristic is b
+                let fans = breakpoints.iter().filter_map(|&b| {
+                    let (below, above) = (foot(lo).sub(b), foot(hi).sub(b));
+                    let opposite = (below <= F::zero()) != (above <= F::zero());
+                    opposite.then(|| bisect(|u| foot(u).sub(b), lo, hi))
+                });
+                let mut candidates: Vec<F> = values.iter().copied().chain(fans).collect();
+                candidates.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                candidates
+            }
+            InitialData::Smooth { grid, .. } => self.characteristic_roots(&foot, grid.delta),
+        };
+
+        // ascending candidates, the smallest one being kept on the shocks where the objective is
+        // minimal at two of them
+        candidates
+            .into_iter()
+            .map(|u: F| (u, objective(u)))
+            .fold(None, |best: Option<(F, F)>, (u, g)| match best {
+                Some((_, min)) if min <= g => best,
+                _ => Some((u, g)),
+            })
+            .map_or(hi, |(u, _)| u)
+    }
+
+    /// Minima of the objective over smooth data: the roots of the characteristic equation where
+    /// it changes sign from negative to positive, or the ends of the range where it starts
+    /// positive or ends negative
+    fn characteristic_roots(&self, foot: &dyn Fn(F) -> F, delta: F) -> Vec<F> {
+        let (lo, hi) = self.range;
+        let characteristic = |u: F| u.sub(self.data.value(foot(u)));
+
+        let mut samples = vec![lo];
+        for k in 1..=CHARACTERISTIC_SAMPLES {
+            let s = F::from_f64(k as f64 / CHARACTERISTIC_SAMPLES as f64);
+            let (a, b) = (samples[samples.len() - 1], lo.add(hi.sub(lo).mul(s)));
+            refine(foot, delta, a, b, CHARACTERISTIC_REFINEMENTS, &mut samples);
+        }
+
+        let mut roots = Vec::new();
+        let mut previous = characteristic(lo);
+        if previous >= F::zero() {
+            roots.push(lo);
+        }
+        for w in samples.windows(2) {
+            let current = characteristic(w[1]);
+            if previous < F::zero() && current >= F::zero() {
+                roots.push(bisect(characteristic, w[0], w[1]));
+            }
+            previous = current;
+        }
+        if previous < F::zero() {
+            roots.push(hi);
+        }
+        roots
+    }
+
+    /// Solution at time `t` on the nodes of `grid`, to be compared with the solution of a
+    /// simulation on [`Mesh::space`](crate::Mesh::space)
+    pub fn eval_on(&self, grid: Grid<F>, t: F) -> Vec<F> {
+        grid.iter().map(|x| self.eval(x, t)).collect()
+    }
+}
+
+/// Appends to `samples` the values up to `b` splitting `[a, b]` until the feet of the
+/// characteristics at the ends of each interval are at most `delta` apart, or `depth` halvings
+fn refine<F: SimpleFloat>(
+    foot: &dyn Fn(F) -> F,
+    delta: F,
+    a: F,
+    b: F,
+    depth: usize,
+    samples: &mut Vec<F>,
+) {
+    if depth > 0 && linalg::abs(foot(b).sub(foot(a))) > delta {
+        let m = a.add(b).mul(F::from_f64(0.5));
+        refine(foot, delta, a, m, depth - 1, samples);
+        refine(foot, delta, m, b, depth - 1, samples);
+    } else {
+        samples.push(b);
+    }
+}
+
+/// Root of `g` between `a` and `b`, where it changes sign, by bisection
+fn bisect<F: SimpleFloat>(g: impl Fn(F) -> F, a: F, b: F) -> F {
+    let half = F::from_f64(0.5);
+    let below = g(a) <= F::zero();
+    let (mut a, mut b) = (a, b);
+    for _ in 0..CHARACTERISTIC_BISECTIONS {
+        let m = a.add(b).mul(half);
+        if (g(m) <= F::zero()) == below {
+            a = m;
+        } else {
+            b = m;
+        }
+    }
+    a.add(b).mul(half)
+}
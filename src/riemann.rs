@@ -0,0 +1,257 @@
+use faer_core::{Mat, MatMut, MatRef};
+
+use crate::{linalg, ConservationLaw, SimpleFloat};
+
+/// Gives the flux through the interface between two states by (approximately) solving the
+/// Riemann problem they define
+pub trait RiemannSolver<F: SimpleFloat> {
+    /// `left` and `right` are single cells of `system_size` rows, the flux at the interface is
+    /// written into `flux`
+    fn interface_flux(
+        &self,
+        cl: &dyn ConservationLaw<F>,
+        left: MatRef<F>,
+        right: MatRef<F>,
+        flux: MatMut<F>,
+    );
+}
+
+/// Evaluates the flux of a scalar conservation law at `u`
+pub(crate) fn scalar_flux<F: SimpleFloat>(cl: &dyn ConservationLaw<F>, u: F) -> F {
+    let mut f = F::zero();
+    cl.flux_function(
+        MatRef::from_column_major_slice(F::to_group(core::slice::from_ref(&u)), 1, 1),
+        MatMut::from_column_major_slice(F::to_group(core::slice::from_mut(&mut f)), 1, 1),
+    );
+    f
+}
+
+/// Evaluates the characteristic speed `f'(u)` of a scalar conservation law
+pub(crate) fn scalar_speed<F: SimpleFloat>(cl: &dyn ConservationLaw<F>, u: F) -> F {
+    let mut a = F::zero();
+    cl.jacobian(
+        MatRef::from_column_major_slice(F::to_group(core::slice::from_ref(&u)), 1, 1),
+        MatMut::from_column_major_slice(F::to_group(core::slice::from_mut(&mut a)), 1, 1),
+    );
+    a
+}
+
+pub(crate) const SONIC_POINT_BISECTIONS: usize = 60;
+
+/// Locates by bisection the sonic point `f'(u*) = 0` between `a` and `b`, where `f'` changes sign
+pub(crate) fn sonic_point<F: SimpleFloat>(cl: &dyn ConservationLaw<F>, a: F, b: F) -> F {
+    let half = F::from_f64(0.5);
+    let increasing = scalar_speed(cl, a) < scalar_speed(cl, b);
+    let (mut a, mut b) = (a, b);
+    for _ in 0..SONIC_POINT_BISECTIONS {
+        let m = a.add(b).mul(half);
+        if (scalar_speed(cl, m) < F::zero()) == increasing {
+            a = m;
+        } else {
+            b = m;
+        }
+    }
+    a.add(b).mul(half)
+}
+
+/// Exact Riemann solver for scalar conservation laws with a convex flux, such as
+/// [`cl::Scalar`](crate::cl::Scalar) with `f(u) = u²/2` (Burgers)
+///
+/// The entropy solution is sampled at the interface: shocks when `u_L > u_R`, rarefactions
+/// otherwise, with the sonic point located by bisection on `f'` for transonic rarefactions.
+///
+/// # Panics
+///
+/// Panics on a system, or when `f'` is decreasing between the states, which rules out concave
+/// fluxes. Other non-convex fluxes, such as the one of
+/// [`models::BuckleyLeverett`](crate::cl::models::BuckleyLeverett), go unnoticed and get a wrong
+/// flux: their Riemann problems mix shocks and rarefactions, see [`Exact`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExactConvexScalar;
+
+impl<F: SimpleFloat> RiemannSolver<F> for ExactConvexScalar {
+    fn interface_flux(
+        &self,
+        cl: &dyn ConservationLaw<F>,
+        left: MatRef<F>,
+        right: MatRef<F>,
+        mut flux: MatMut<F>,
+    ) {
+        assert_eq!(
+            cl.system_size(),
+            1,
+            "the exact convex scalar Riemann solver needs a scalar conservation law"
+        );
+        let (ul, ur) = (left.read(0, 0), right.read(0, 0));
+        let (fl, fr) = (scalar_flux(cl, ul), scalar_flux(cl, ur));
+        let (sl, sr) = (scalar_speed(cl, ul), scalar_speed(cl, ur));
+        // up to the accuracy of finite difference derivatives
+        let tolerance = linalg::epsilon::<F>()
+            .sqrt()
+            .mul(F::one().add(linalg::abs(sl)).add(linalg::abs(sr)));
+        let (slow, fast) = if ul < ur { (sl, sr) } else { (sr, sl) };
+        assert!(
+            slow.sub(fast) <= tolerance,
+            "the flux is not convex between the states"
+        );
+
+        let f = if ul > ur {
+            // shock, the flux is the one of the upwind state
+            let speed = fl.sub(fr).div(ul.sub(ur));
+            if speed > F::zero() {
+                fl
+            } else {
+                fr
+            }
+        } else if sl >= F::zero() {
+            fl
+        } else if sr <= F::zero() {
+            fr
+        } else {
+            // transonic rarefaction
+            scalar_flux(cl, sonic_point(cl, ul, ur))
+        };
+
+        flux.write(0, 0, f);
+    }
+}
+
+/// Conservation laws whose Riemann problems have a known exact solution
+pub trait ExactRiemannSolution<F: SimpleFloat> {
+    /// Writes into `u` the self-similar solution at `ξ = x/t` of the Riemann problem between the
+    /// states `left` and `right`, all single cells of `system_size` rows
+    fn riemann_sample(&self, left: MatRef<F>, right: MatRef<F>, xi: F, u: MatMut<F>);
+}
+
+/// Nonlinear wave of the exact solution of a Riemann problem of a system, the left wave
+/// travelling into the left state and the right wave into the right state
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Wave<F> {
+    Shock {
+        speed: F,
+    },
+    /// Rarefaction fan, from its `head` bordering the undisturbed state to its `tail`
+    Rarefaction {
+        head: F,
+        tail: F,
+    },
+}
+
+impl<F: SimpleFloat> Wave<F> {
+    pub fn is_shock(&self) -> bool {
+        matches!(self, Wave::Shock { .. })
+    }
+
+    /// Edge of the wave bordering the star region: the shock itself, or the tail of the fan
+    pub fn inner_speed(&self) -> F {
+        match *self {
+            Wave::Shock { speed } => speed,
+            Wave::Rarefaction { tail, .. } => tail,
+        }
+    }
+
+    /// Edge of the wave bordering the undisturbed state: the shock itself, or the head of the fan
+    pub fn outer_speed(&self) -> F {
+        match *self {
+            Wave::Shock { speed } => speed,
+            Wave::Rarefaction { head, .. } => head,
+        }
+    }
+}
+
+/// Exact Riemann solver of a conservation law `E` with a known exact solution, such as the ones
+/// of [`cl::models`](crate::cl::models), sampled at the interface
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Exact<E>(pub E);
+
+impl<F: SimpleFloat, E: ExactRiemannSolution<F>> RiemannSolver<F> for Exact<E> {
+    fn interface_flux(
+        &self,
+        cl: &dyn ConservationLaw<F>,
+        left: MatRef<F>,
+        right: MatRef<F>,
+        flux: MatMut<F>,
+    ) {
+        let mut u = Mat::<F>::zeros(cl.system_size(), 1);
+        self.0.riemann_sample(left, right, F::zero(), u.as_mut());
+        cl.flux_function(u.as_ref(), flux);
+    }
+}
+
+/// Samples at `ξ` the entropy solution of the scalar Riemann problem between `ul` and `ur` for a
+/// flux `f` of derivative `df`, convex below the `inflection` point and concave above it, such as
+/// the S-shaped fluxes of two-phase flow (Oleinik's construction)
+///
+/// Between `ul < ur`, the solution follows the lower convex envelope of `f`: a rarefaction over
+/// the convex part up to the point `u*` where the chord to `ur` is tangent to `f`,
+/// `f'(u*) = (f(ur) - f(u*))/(ur - u*)`, then a shock to `ur` at the speed `f'(u*)`. Both the
+/// tangency and the inverse of `f'` inside the rarefaction are solved by bisection. Between
+/// `ul > ur`, the solution follows the upper concave envelope, by symmetry.
+pub(crate) fn envelope_sample<F: SimpleFloat>(
+    f: &dyn Fn(F) -> F,
+    df: &dyn Fn(F) -> F,
+    inflection: F,
+    ul: F,
+    ur: F,
+    xi: F,
+) -> F {
+    if ul > ur {
+        // w = -u solves w_t + g(w)_x = 0 with g(w) = -f(-w), again convex below -inflection
+        let g = |w: F| f(w.neg()).neg();
+        let dg = |w: F| df(w.neg());
+        return envelope_sample(&g, &dg, inflection.neg(), ul.neg(), ur.neg(), xi).neg();
+    }
+    if ul == ur {
+        return ul;
+    }
+
+    let half = F::from_f64(0.5);
+    let chord = |a: F, b: F| f(b).sub(f(a)).div(b.sub(a));
+    // tangency residual, negative while the chord from u to ur cuts below f near u
+    let tangency = |u: F| df(u).sub(chord(u, ur));
+
+    // end of the rarefaction over the convex part, and whether a shock follows it
+    let (tail, shock) = if ur <= inflection {
+        (ur, false)
+    } else if ul >= inflection || tangency(ul) >= F::zero() {
+        (ul, true)
+    } else if tangency(inflection) <= F::zero() {
+        (inflection, true)
+    } else {
+        let (mut a, mut b) = (ul, inflection);
+        for _ in 0..SONIC_POINT_BISECTIONS {
+            let m = a.add(b).mul(half);
+            if tangency(m) < F::zero() {
+                a = m;
+            } else {
+                b = m;
+            }
+        }
+        (a.add(b).mul(half), true)
+    };
+
+    if shock {
+        let speed = chord(tail, ur);
+        if xi >= speed {
+            return ur;
+        }
+    }
+    if xi <= df(ul) {
+        return ul;
+    }
+    if xi >= df(tail) {
+        return tail;
+    }
+
+    // inside the rarefaction, f' is increasing and inverted by bisection
+    let (mut a, mut b) = (ul, tail);
+    for _ in 0..SONIC_POINT_BISECTIONS {
+        let m = a.add(b).mul(half);
+        if df(m) < xi {
+            a = m;
+        } else {
+            b = m;
+        }
+    }
+    a.add(b).mul(half)
+}
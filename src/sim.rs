@@ -14,11 +14,38 @@ pub enum Resolution<F> {
     Steps(usize),
 }
 
+/// How the time step is chosen along the simulation
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TimeStepping<F> {
+    /// Constant time step given by the time resolution of the mesh
+    #[default]
+    Fixed,
+    /// Time step adapted at each iteration so that `Δt/Δx · max|f'(u)|` equals the given CFL
+    /// number, up to the nominal time step of the mesh, the last step landing on the end of the
+    /// time domain
+    Cfl(F),
+}
+
+/// How the source of a balance law is integrated along with the method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Splitting {
+    /// Source added to the right-hand side of each stage of a
+    /// [`MethodOfLines`](crate::methods::MethodOfLines), to the order of its integrator; for other
+    /// methods, forward Euler source step `Δt s(u)` added to their update, first order in time
+    #[default]
+    Unsplit,
+    /// Strang splitting: half a step of source, a step of the method and half a step of source,
+    /// each source half step integrated by Heun's method, second order in time
+    Strang,
+}
+
 #[derive(Debug, Clone)]
 pub struct Simulation<'pb, F: SimpleFloat, M> {
     pub(crate) problem: Problem<'pb, F>,
     pub(crate) mesh: Mesh<F>,
     pub(crate) method: M,
+    pub(crate) time_stepping: TimeStepping<F>,
+    pub(crate) splitting: Splitting,
 }
 
 impl<'pb, F: SimpleFloat> Simulation<'pb, F, methods::MacCormack<F>> {
@@ -32,6 +59,8 @@ impl<'pb, F: SimpleFloat> Simulation<'pb, F, methods::MacCormack<F>> {
             problem,
             mesh,
             method: methods::MacCormack::default(),
+            time_stepping: TimeStepping::Fixed,
+            splitting: Splitting::Unsplit,
         }
     }
 }
@@ -61,11 +90,31 @@ impl<'pb, F: SimpleFloat, M: Method<F>> Simulation<'pb, F, M> {
         self
     }
 
+    /// With [`TimeStepping::Cfl`], the time resolution only sets the nominal time step, which
+    /// bounds the adaptive ones and converts sampling periods
+    pub fn with_time_stepping(mut self, time_stepping: TimeStepping<F>) -> Self {
+        self.time_stepping = time_stepping;
+        self
+    }
+
+    /// Only relevant for problems with a source term
+    pub fn with_splitting(mut self, splitting: Splitting) -> Self {
+        self.splitting = splitting;
+        self
+    }
+
     pub fn with_method<N: Method<F> + Default>(self) -> Simulation<'pb, F, N> {
+        self.with_method_instance(N::default())
+    }
+
+    /// Same as [`Simulation::with_method`] for methods that need parameters
+    pub fn with_method_instance<N: Method<F>>(self, method: N) -> Simulation<'pb, F, N> {
         Simulation {
             problem: self.problem,
             mesh: self.mesh,
-            method: N::default(),
+            method,
+            time_stepping: self.time_stepping,
+            splitting: self.splitting,
         }
     }
 }
@@ -74,13 +123,26 @@ impl<F: SimpleFloat + fmt::LowerExp, M: Method<F>> fmt::Display for Simulation<'
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "simulation of `{}` problem:\n\t- `{}` method\n\t- Δx = {:e} ({} steps)\n\t- Δt = {:e} ({} steps)",
+            "simulation of `{}` problem:\n\t- `{}` method\n\t- Δx = {:e} ({} steps)",
             self.problem.name,
             self.method.name(),
             self.mesh.space.delta,
             self.mesh.space.steps,
-            self.mesh.time.delta,
-            self.mesh.time.steps
-        )
+        )?;
+        match self.time_stepping {
+            TimeStepping::Fixed => write!(
+                f,
+                "\n\t- Δt = {:e} ({} steps)",
+                self.mesh.time.delta, self.mesh.time.steps
+            ),
+            TimeStepping::Cfl(cfl) => write!(f, "\n\t- adaptive Δt (CFL = {:e})", cfl),
+        }?;
+        if self.problem.source.is_some() {
+            match self.splitting {
+                Splitting::Unsplit => write!(f, "\n\t- unsplit source term")?,
+                Splitting::Strang => write!(f, "\n\t- Strang-split source term")?,
+            }
+        }
+        Ok(())
     }
 }
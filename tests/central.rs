@@ -0,0 +1,132 @@
+mod common;
+
+use conlaw::{
+    bc, cl,
+    methods::{self, Integrator, KurganovTadmorOperator, Limiter, MethodOfLines},
+    Domain, Method, Problem, Resolution, Simulation,
+};
+
+use common::{advection_orders, component, l1_error, run};
+
+fn nessyahu_tadmor(limiter: Limiter) -> methods::NessyahuTadmor<f64> {
+    methods::NessyahuTadmor::new(limiter)
+}
+
+fn kurganov_tadmor(limiter: Limiter) -> methods::KurganovTadmor<f64> {
+    MethodOfLines::new(KurganovTadmorOperator::new(limiter), Integrator::SspRk3)
+}
+
+#[test]
+fn second_order() {
+    for (name, orders) in [
+        (
+            "Nessyahu-Tadmor",
+            advection_orders(|| nessyahu_tadmor(Limiter::MC), 25, 0.4),
+        ),
+        (
+            "Kurganov-Tadmor",
+            advection_orders(|| kurganov_tadmor(Limiter::MC), 25, 0.4),
+        ),
+    ] {
+        assert!(orders.iter().all(|&p| p > 1.7), "{name}: orders {orders:?}");
+    }
+}
+
+/// Final solution of `method` advecting a square wave once around the periodic domain
+fn square_wave<M: Method<f64>>(method: M) -> Vec<f64> {
+    let problem = Problem::new(
+        "square_wave",
+        cl::Scalar::new(|u: f64| u),
+        Domain {
+            time: (0., 1.),
+            space: (0., 1.),
+        },
+        bc::Periodic,
+        |x, mut v| v[(0, 0)] = if (0.25..0.5).contains(&x) { 1. } else { 0. },
+    );
+    let sim = Simulation::new(problem)
+        .with_method_instance(method)
+        .with_time_resolution(Resolution::Steps(250))
+        .with_space_resolution(Resolution::Steps(100));
+    run(sim)
+}
+
+#[test]
+fn non_oscillatory_and_conservative() {
+    for (name, u) in [
+        (
+            "Nessyahu-Tadmor",
+            square_wave(nessyahu_tadmor(Limiter::Minmod)),
+        ),
+        (
+            "Kurganov-Tadmor",
+            square_wave(kurganov_tadmor(Limiter::Minmod)),
+        ),
+    ] {
+        let (min, max) = u
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &u| {
+                (min.min(u), max.max(u))
+            });
+        assert!(
+            min > -1e-12 && max < 1. + 1e-12,
+            "{name}: values in [{min}, {max}]"
+        );
+        // the cells 0 and 100 are the same point of the periodic domain
+        let mass = u[..100].iter().sum::<f64>() / 100.;
+        assert!((mass - 0.25).abs() < 1e-12, "{name}: mass {mass}");
+    }
+}
+
+/// L1 error of the density of `method` on Sod's shock tube
+fn sod_error<M: Method<f64>>(method: M) -> f64 {
+    let euler = cl::Euler::<f64>::default();
+    let (left, right) = (
+        cl::Primitive {
+            density: 1.,
+            velocity: 0.,
+            pressure: 1.,
+        },
+        cl::Primitive {
+            density: 0.125,
+            velocity: 0.,
+            pressure: 0.1,
+        },
+    );
+    let problem = Problem::new(
+        "sod",
+        euler,
+        Domain {
+            time: (0., 0.2),
+            space: (0., 1.),
+        },
+        bc::Outflow::constant(),
+        move |x, v| euler.conserved(if x < 0.5 { &left } else { &right }, v),
+    );
+    let sim = Simulation::new(problem)
+        .with_method_instance(method)
+        .with_time_resolution(Resolution::Steps(400))
+        .with_space_resolution(Resolution::Steps(200));
+
+    let density = component(&run(sim), 3, 0);
+    let solution = euler.riemann(&left, &right);
+    let exact: Vec<f64> = (0..=200)
+        .map(|i| solution.sample((i as f64 / 200. - 0.5) / 0.2).density)
+        .collect();
+    l1_error(&density, &exact)
+}
+
+#[test]
+fn sod() {
+    // no Riemann solver nor characteristic decomposition is needed for systems
+    let nt = sod_error(methods::NessyahuTadmor::<f64>::default());
+    let kt = sod_error(methods::KurganovTadmor::<f64>::default());
+    let rusanov = sod_error(methods::FiniteVolume::new(conlaw::fluxes::Rusanov));
+    for (name, error) in [("Nessyahu-Tadmor", nt), ("Kurganov-Tadmor", kt)] {
+        assert!(error < 1.5e-2, "{name}: error {error}");
+        assert!(
+            error < rusanov,
+            "{name}: error {error}, {rusanov} with Rusanov"
+        );
+    }
+}
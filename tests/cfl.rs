@@ -0,0 +1,119 @@
+use conlaw::{
+    bc, cl, methods, CflPolicy, Domain, Driver, Method, Problem, Resolution, SimError, SimpleFloat,
+    Simulation,
+};
+
+/// Advection at unit speed on `[0, 1]` with 100 cells until `t = 0.08`, at the CFL number
+/// `0.08 / time_steps / 0.01`
+fn advection<F: SimpleFloat + Into<f64>, M: Method<F> + Default>(
+    time_steps: usize,
+    policy: CflPolicy,
+) -> Result<(), SimError> {
+    let problem = Problem::new(
+        "advection",
+        cl::Scalar::new(|u: F| u),
+        Domain {
+            time: (F::zero(), F::from_f64(0.08)),
+            space: (F::zero(), F::one()),
+        },
+        bc::Periodic,
+        |x: F, mut v: faer_core::MatMut<F>| v.write(0, 0, x.mul(x)),
+    );
+    let sim = Simulation::new(problem)
+        .with_method::<M>()
+        .with_time_resolution(Resolution::Steps(time_steps))
+        .with_space_resolution(Resolution::Steps(100));
+    Driver::new(sim).with_cfl_check(policy, false).run()
+}
+
+fn cfl_violation(result: Result<(), SimError>) -> (usize, f64, f64) {
+    match result {
+        Err(SimError::CflViolated { iter, cfl, limit }) => (iter, cfl, limit),
+        other => panic!("expected a CFL violation, got {other:?}"),
+    }
+}
+
+#[test]
+fn within_limit() {
+    advection::<f64, methods::LaxFriedrichs<_>>(10, CflPolicy::Abort)
+        .expect("CFL number 0.8 is stable");
+}
+
+#[test]
+fn violation_aborts() {
+    let (iter, cfl, limit) = cfl_violation(advection::<f64, methods::LaxFriedrichs<_>>(
+        4,
+        CflPolicy::Abort,
+    ));
+    assert_eq!(iter, 0);
+    assert!((cfl - 2.).abs() < 1e-12, "CFL number {cfl}");
+    assert_eq!(limit, 1.);
+}
+
+#[test]
+fn violation_ignored() {
+    advection::<f64, methods::LaxFriedrichs<_>>(4, CflPolicy::Ignore)
+        .expect("CFL violations are ignored");
+    advection::<f64, methods::LaxFriedrichs<_>>(4, CflPolicy::Warn)
+        .expect("CFL violations only warn");
+}
+
+#[test]
+fn single_precision() {
+    let (_, cfl, limit) = cfl_violation(advection::<f32, methods::LaxFriedrichs<_>>(
+        4,
+        CflPolicy::Abort,
+    ));
+    // up to the finite difference approximation of the wave speed in single precision
+    assert!((cfl - 2.).abs() < 1e-3, "CFL number {cfl}");
+    assert_eq!(limit, 1.);
+}
+
+#[test]
+fn method_limits() {
+    // stable at 0.8 for the one-step schemes, not for the second-order ones limited to 0.5
+    advection::<f64, methods::LaxWendroff<_>>(10, CflPolicy::Abort).expect("limit of 1");
+    for result in [
+        advection::<f64, methods::Muscl<_>>(10, CflPolicy::Abort),
+        advection::<f64, methods::NessyahuTadmor<_>>(10, CflPolicy::Abort),
+        advection::<f64, methods::KurganovTadmor<_>>(10, CflPolicy::Abort),
+        advection::<f64, methods::Weno5<_>>(10, CflPolicy::Abort),
+    ] {
+        assert_eq!(cfl_violation(result).2, 0.5);
+    }
+    advection::<f64, methods::Weno5<_>>(20, CflPolicy::Abort).expect("CFL number 0.4");
+}
+
+/// Burgers' equation with a fast state entering from the left: the CFL number is 0.2 on the
+/// initial condition and reaches 2 later on
+fn inflow() -> Simulation<'static, f64, methods::LaxFriedrichs<f64>> {
+    let problem = Problem::new(
+        "inflow",
+        cl::Scalar::new(|u: f64| 0.5 * u * u),
+        Domain {
+            time: (0., 0.2),
+            space: (0., 1.),
+        },
+        bc::Dirichlet::new(|_, mut v| v[(0, 0)] = 1., |_, mut v| v[(0, 0)] = 0.1),
+        |_, mut v| v[(0, 0)] = 0.1,
+    );
+    Simulation::new(problem)
+        .with_method::<methods::LaxFriedrichs<_>>()
+        .with_time_resolution(Resolution::Steps(10))
+        .with_space_resolution(Resolution::Steps(100))
+}
+
+#[test]
+fn checked_at_each_sample() {
+    Driver::new(inflow())
+        .with_cfl_check(CflPolicy::Abort, false)
+        .run()
+        .expect("only the initial condition is checked");
+    let (iter, cfl, _) = cfl_violation(
+        Driver::new(inflow())
+            .with_time_sampling(Resolution::Steps(1))
+            .with_cfl_check(CflPolicy::Abort, true)
+            .run(),
+    );
+    assert!(iter > 0 && cfl > 1., "step {iter}, CFL number {cfl}");
+}
@@ -0,0 +1,200 @@
+mod common;
+
+use faer_core::{MatMut, MatRef};
+
+use conlaw::{
+    bc, cl,
+    methods::{self, Average, Fallback, Limiter, Variables, Weno5Operator},
+    Domain, Method, Problem, Resolution, Simulation,
+};
+
+use common::{component, l1_error, run};
+
+/// Density of `method` on Sod's shock tube, with its L1 error
+fn sod<M: Method<f64>>(method: M) -> (Vec<f64>, f64) {
+    let euler = cl::Euler::<f64>::default();
+    let (left, right) = (
+        cl::Primitive {
+            density: 1.,
+            velocity: 0.,
+            pressure: 1.,
+        },
+        cl::Primitive {
+            density: 0.125,
+            velocity: 0.,
+            pressure: 0.1,
+        },
+    );
+    let problem = Problem::new(
+        "sod",
+        euler,
+        Domain {
+            time: (0., 0.2),
+            space: (0., 1.),
+        },
+        bc::Outflow::constant(),
+        move |x, v| euler.conserved(if x < 0.5 { &left } else { &right }, v),
+    );
+    let sim = Simulation::new(problem)
+        .with_method_instance(method)
+        .with_time_resolution(Resolution::Steps(100))
+        .with_space_resolution(Resolution::Steps(100));
+
+    let density = component(&run(sim), 3, 0);
+    let solution = euler.riemann(&left, &right);
+    let exact: Vec<f64> = (0..=100)
+        .map(|i| solution.sample((i as f64 / 100. - 0.5) / 0.2).density)
+        .collect();
+    let error = l1_error(&density, &exact);
+    (density, error)
+}
+
+fn weno5(variables: Variables) -> methods::Weno5<f64> {
+    methods::Weno5::new(Weno5Operator::new(variables), Default::default())
+}
+
+fn muscl(variables: Variables) -> methods::Muscl<f64> {
+    methods::Muscl::new(conlaw::fluxes::Rusanov, Limiter::MC).with_variables(variables)
+}
+
+/// Total variation of `u`
+fn total_variation(u: &[f64]) -> f64 {
+    u.windows(2).map(|w| (w[1] - w[0]).abs()).sum()
+}
+
+#[test]
+fn fewer_oscillations() {
+    // the exact density is monotone, of total variation 0.875
+    let roe = Variables::Characteristic {
+        average: Average::EulerRoe,
+        fallback: Fallback::Panic,
+    };
+    for (name, conserved, characteristic) in [
+        ("WENO5", sod(weno5(Variables::Conserved)), sod(weno5(roe))),
+        ("MUSCL", sod(muscl(Variables::Conserved)), sod(muscl(roe))),
+    ] {
+        let (tv, tv_characteristic) = (
+            total_variation(&conserved.0),
+            total_variation(&characteristic.0),
+        );
+        assert!(
+            tv_characteristic < tv && tv_characteristic < 0.875 + 5e-3,
+            "{name}: total variation {tv_characteristic}, {tv} in conserved variables"
+        );
+        assert!(
+            characteristic.1 < 2e-2,
+            "{name}: error {}",
+            characteristic.1
+        );
+    }
+}
+
+#[test]
+fn averages() {
+    // the Roe and arithmetic averages agree on smooth parts and differ little at discontinuities
+    let roe = Variables::Characteristic {
+        average: Average::EulerRoe,
+        fallback: Fallback::Conserved,
+    };
+    for (name, (arithmetic, arithmetic_error), (roe, roe_error)) in [
+        (
+            "WENO5",
+            sod(weno5(Variables::characteristic())),
+            sod(weno5(roe)),
+        ),
+        (
+            "MUSCL",
+            sod(muscl(Variables::characteristic())),
+            sod(muscl(roe)),
+        ),
+    ] {
+        assert!(arithmetic != roe, "{name}: same solutions");
+        assert!(
+            (arithmetic_error - roe_error).abs() < 0.2 * roe_error,
+            "{name}: errors {arithmetic_error} and {roe_error}"
+        );
+    }
+}
+
+/// Two components advected at unit speed by a Jordan block, whose Jacobian is never
+/// diagonalizable
+fn jordan(variables: Variables) -> Vec<f64> {
+    let problem = Problem::new(
+        "jordan",
+        cl::General::new(2, |u: MatRef<f64>, mut v: MatMut<f64>| {
+            v[(0, 0)] = u[(0, 0)] + u[(1, 0)];
+            v[(1, 0)] = u[(1, 0)];
+        }),
+        Domain {
+            time: (0., 0.1),
+            space: (0., 1.),
+        },
+        bc::Periodic,
+        |x, mut v| {
+            v[(0, 0)] = if x < 0.5 { 1. } else { 0. };
+            v[(1, 0)] = (2. * std::f64::consts::PI * x).sin();
+        },
+    );
+    let sim = Simulation::new(problem)
+        .with_method_instance(muscl(variables))
+        .with_time_resolution(Resolution::Steps(25))
+        .with_space_resolution(Resolution::Steps(50));
+    run(sim)
+}
+
+#[test]
+fn conserved_fallback() {
+    assert_eq!(
+        jordan(Variables::characteristic()),
+        jordan(Variables::Conserved)
+    );
+}
+
+#[test]
+#[should_panic(expected = "not diagonalizable")]
+fn panic_fallback() {
+    jordan(Variables::Characteristic {
+        average: Average::Arithmetic,
+        fallback: Fallback::Panic,
+    });
+}
+
+#[test]
+fn shallow_water_dam_break() {
+    let sw = cl::ShallowWater::<f64>::default();
+    let (left, right) = (
+        cl::Flow {
+            depth: 2.,
+            velocity: 0.,
+        },
+        cl::Flow {
+            depth: 1.,
+            velocity: 0.,
+        },
+    );
+    let problem = Problem::new(
+        "dam_break",
+        sw,
+        Domain {
+            time: (0., 0.05),
+            space: (0., 1.),
+        },
+        bc::Outflow::constant(),
+        move |x, v| sw.conserved(if x < 0.5 { &left } else { &right }, v),
+    );
+    let sim = Simulation::new(problem)
+        .with_method_instance(muscl(Variables::Characteristic {
+            average: Average::ShallowWaterRoe,
+            fallback: Fallback::Panic,
+        }))
+        .with_time_resolution(Resolution::Steps(200))
+        .with_space_resolution(Resolution::Steps(100));
+
+    let depth = component(&run(sim), 2, 0);
+    let solution = sw.riemann(&left, &right);
+    let exact: Vec<f64> = (0..=100)
+        .map(|i| solution.sample((i as f64 / 100. - 0.5) / 0.05).depth)
+        .collect();
+    let error = l1_error(&depth, &exact);
+    assert!(error < 2e-2, "error {error}");
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use faer_core::{MatMut, MatRef};
+
+use conlaw::{bc, cl, methods, Domain, Problem, Resolution, Simulation};
+
+use common::{assert_cell, assert_cell_within, assert_close, component, ghost_cells, run};
+
+/// Linear acoustics `p_t + v_x = 0`, `v_t + p_x = 0`, whose characteristic variables
+/// `(p - v)/2` and `(p + v)/2` travel at speeds -1 and 1
+fn acoustics(u: MatRef<f64>, mut v: MatMut<f64>) {
+    v[(0, 0)] = u[(1, 0)];
+    v[(1, 0)] = u[(0, 0)];
+}
+
+fn farfield(_t: f64, mut v: MatMut<f64>) {
+    v[(0, 0)] = 5.;
+    v[(1, 0)] = 7.;
+}
+
+#[test]
+fn incoming_from_farfield() {
+    let problem = Problem::new(
+        "characteristic",
+        cl::General::new(2, acoustics),
+        Domain {
+            time: (0., 1.),
+            space: (0., 1.),
+        },
+        bc::Characteristic::new(farfield),
+        |x, mut v| {
+            v[(0, 0)] = 1. + x;
+            v[(1, 0)] = 2. + x;
+        },
+    );
+
+    let ghosts = &ghost_cells(problem, 1, 10)[0];
+    for k in 0..2 {
+        // left: (p - v)/2 = -0.5 from the boundary cell (1, 2), (p + v)/2 = 6 from the far field
+        assert_cell_within(&ghosts.left[k], &[5.5, 6.5], 1e-6);
+        // right: (p - v)/2 = -1 from the far field, (p + v)/2 = 2.5 from the boundary cell (2, 3)
+        assert_cell_within(&ghosts.right[k], &[1.5, 3.5], 1e-6);
+    }
+}
+
+#[test]
+fn not_diagonalizable() {
+    // the Jacobian is a Jordan block, the boundary cells are extrapolated
+    let problem = Problem::new(
+        "characteristic_jordan",
+        cl::General::new(2, |u: MatRef<f64>, mut v: MatMut<f64>| {
+            v[(0, 0)] = u[(1, 0)];
+            v[(1, 0)] = 0.;
+        }),
+        Domain {
+            time: (0., 1.),
+            space: (0., 1.),
+        },
+        bc::Characteristic::new(farfield),
+        |x, mut v| {
+            v[(0, 0)] = 1. + x;
+            v[(1, 0)] = 2. + x;
+        },
+    );
+
+    let ghosts = &ghost_cells(problem, 1, 10)[0];
+    for k in 0..2 {
+        assert_cell(&ghosts.left[k], &[1., 2.]);
+        assert_cell(&ghosts.right[k], &[2., 3.]);
+    }
+}
+
+#[test]
+fn dry_shallow_water() {
+    let problem = Problem::new(
+        "characteristic_dry",
+        cl::ShallowWater::default(),
+        Domain {
+            time: (0., 1.),
+            space: (0., 1.),
+        },
+        bc::Characteristic::new(|_, mut v: MatMut<f64>| {
+            v[(0, 0)] = 1.;
+            v[(1, 0)] = 0.;
+        }),
+        |x, mut v| {
+            v[(0, 0)] = x;
+            v[(1, 0)] = 0.;
+        },
+    );
+
+    let ghosts = &ghost_cells(problem, 1, 10)[0];
+    for k in 0..2 {
+        // dry on the left, wet on the right where the far field is at rest with the same depth
+        assert_cell(&ghosts.left[k], &[0., 0.]);
+        assert_cell_within(&ghosts.right[k], &[1., 0.], 1e-12);
+    }
+}
+
+#[test]
+fn non_reflecting() {
+    // a right-going pulse leaving the domain, no wave coming back
+    let pulse = |x: f64| (-((x - 0.5) / 0.1).powi(2)).exp();
+    let problem = Problem::new(
+        "characteristic_pulse",
+        cl::General::new(2, acoustics),
+        Domain {
+            time: (0., 1.),
+            space: (0., 1.),
+        },
+        bc::Characteristic::new(|_, mut v: MatMut<f64>| {
+            v[(0, 0)] = 0.;
+            v[(1, 0)] = 0.;
+        }),
+        move |x, mut v| {
+            v[(0, 0)] = pulse(x);
+            v[(1, 0)] = pulse(x);
+        },
+    );
+
+    let sim = Simulation::new(problem)
+        .with_method::<methods::LaxWendroff<_>>()
+        .with_time_resolution(Resolution::Steps(200))
+        .with_space_resolution(Resolution::Steps(100));
+
+    let u = run(sim);
+    assert_close(&component(&u, 2, 0), 0..101, 0., 0.01);
+    assert_close(&component(&u, 2, 1), 0..101, 0., 0.01);
+}
@@ -0,0 +1,180 @@
+//! Helpers shared by the integration tests
+
+#![allow(dead_code)]
+
+use std::{cell::RefCell, rc::Rc};
+
+use faer_core::{MatMut, MatRef};
+
+use conlaw::{
+    ConservationLaw, Ctx, Driver, Method, ObsCtx, Observer, Problem, Resolution, SimError,
+    Simulation,
+};
+
+/// Keeps a copy of the final solution
+pub struct Final<'a>(pub &'a mut Vec<f64>);
+
+impl Observer<f64> for Final<'_> {
+    fn at_cleanup(&mut self, ctx: ObsCtx<f64>) -> Result<(), SimError> {
+        let u = ctx.solution();
+        self.0.extend((0..u.nrows()).map(|i| u.read(i, 0)));
+        Ok(())
+    }
+}
+
+/// Runs `sim` to the end and returns its final solution
+pub fn run<M: Method<f64>>(sim: Simulation<f64, M>) -> Vec<f64> {
+    let mut solution = Vec::new();
+    Driver::new(sim)
+        .with_observer(Final(&mut solution))
+        .run()
+        .expect("failed to run simulation");
+    solution
+}
+
+/// Component `k` of a solution of `m` components per cell
+pub fn component(u: &[f64], m: usize, k: usize) -> Vec<f64> {
+    u.chunks(m).map(|cell| cell[k]).collect()
+}
+
+/// Mean absolute difference between `u` and `exact`, a discrete L1 norm on a uniform mesh
+pub fn l1_error(u: &[f64], exact: &[f64]) -> f64 {
+    assert_eq!(u.len(), exact.len());
+    u.iter().zip(exact).map(|(u, e)| (u - e).abs()).sum::<f64>() / u.len() as f64
+}
+
+/// Observed orders of convergence between successive errors, the mesh being refined by a factor
+/// 2 each time
+pub fn orders(errors: &[f64]) -> Vec<f64> {
+    errors.windows(2).map(|e| (e[0] / e[1]).log2()).collect()
+}
+
+pub fn assert_close(u: &[f64], cells: std::ops::Range<usize>, value: f64, tolerance: f64) {
+    for i in cells {
+        assert!(
+            (u[i] - value).abs() < tolerance,
+            "u[{i}] = {} instead of {value}",
+            u[i]
+        );
+    }
+}
+
+/// Ghost cells seen by a method at one step, `left[k]` and `right[k]` being the cells at distance
+/// `k + 1` from the boundary cells
+#[derive(Debug, Clone, PartialEq)]
+pub struct GhostCells {
+    /// Time at which the ghost cells were filled, the beginning of the step
+    pub t: f64,
+    pub left: [Vec<f64>; 2],
+    pub right: [Vec<f64>; 2],
+}
+
+/// Method with two ghost cells on each side recording them at every step, and otherwise leaving
+/// the solution unchanged
+struct Probe(Rc<RefCell<Vec<GhostCells>>>);
+
+impl Method<f64> for Probe {
+    fn left_ghost_cells(&self) -> usize {
+        2
+    }
+
+    fn right_ghost_cells(&self) -> usize {
+        2
+    }
+
+    fn init(&mut self, _ctx: Ctx<f64>) {}
+
+    fn apply<'m, 'pb>(
+        &'m mut self,
+        ctx: Ctx<f64>,
+        _flux: Rc<dyn ConservationLaw<f64> + 'pb>,
+        u: MatRef<f64>,
+        mut v: MatMut<f64>,
+    ) {
+        let m = ctx.system_size;
+        let first = |x: MatRef<f64>| (0..m).map(|i| x.read(i, 0)).collect();
+        let last = |x: MatRef<f64>| (x.nrows() - m..x.nrows()).map(|i| x.read(i, 0)).collect();
+        self.0.borrow_mut().push(GhostCells {
+            t: ctx.t - ctx.dt,
+            left: [first(ctx.left()), first(ctx.left2())],
+            right: [last(ctx.right()), last(ctx.right2())],
+        });
+        v.clone_from(u);
+    }
+
+    fn name(&self) -> &'static str {
+        "Probe"
+    }
+}
+
+/// Ghost cells filled by the boundary condition of `problem` at each of `time_steps` steps, on a
+/// mesh of `space_steps` steps
+pub fn ghost_cells(
+    problem: Problem<f64>,
+    time_steps: usize,
+    space_steps: usize,
+) -> Vec<GhostCells> {
+    let record = Rc::new(RefCell::new(Vec::new()));
+    let sim = Simulation::new(problem)
+        .with_method_instance(Probe(record.clone()))
+        .with_time_resolution(Resolution::Steps(time_steps))
+        .with_space_resolution(Resolution::Steps(space_steps));
+    run(sim);
+    let ghosts = record.borrow().clone();
+    ghosts
+}
+
+/// Asserts that two cells agree to round-off
+pub fn assert_cell(cell: &[f64], expected: &[f64]) {
+    assert_cell_within(cell, expected, 1e-12)
+}
+
+/// Asserts that two cells agree to `tolerance`
+pub fn assert_cell_within(cell: &[f64], expected: &[f64], tolerance: f64) {
+    assert_eq!(cell.len(), expected.len());
+    for (k, (c, e)) in cell.iter().zip(expected).enumerate() {
+        assert!(
+            (c - e).abs() < tolerance,
+            "component {k} is {c} instead of {e}"
+        );
+    }
+}
+
+/// L1 error of `method` advecting `sin(2πx)` at unit speed once around the periodic domain
+/// `[0, 1]`, on `space_steps` cells at the Courant number `cfl`
+pub fn advection_error<M: Method<f64>>(method: M, space_steps: usize, cfl: f64) -> f64 {
+    let problem = Problem::new(
+        "smooth_advection",
+        conlaw::cl::Scalar::new(|u: f64| u),
+        conlaw::Domain {
+            time: (0., 1.),
+            space: (0., 1.),
+        },
+        conlaw::bc::Periodic,
+        |x, mut v| v[(0, 0)] = (2. * std::f64::consts::PI * x).sin(),
+    );
+    let time_steps = (space_steps as f64 / cfl).ceil() as usize;
+    let sim = Simulation::new(problem)
+        .with_method_instance(method)
+        .with_time_resolution(Resolution::Steps(time_steps))
+        .with_space_resolution(Resolution::Steps(space_steps));
+
+    let u = run(sim);
+    let exact: Vec<f64> = (0..=space_steps)
+        .map(|i| (2. * std::f64::consts::PI * i as f64 / space_steps as f64).sin())
+        .collect();
+    l1_error(&u, &exact)
+}
+
+/// Observed orders of convergence of the methods built by `method` on smooth advection, on
+/// meshes of `coarsest` cells refined 3 times
+pub fn advection_orders<M: Method<f64>>(
+    method: impl Fn() -> M,
+    coarsest: usize,
+    cfl: f64,
+) -> Vec<f64> {
+    let errors: Vec<f64> = (0..4)
+        .map(|k| advection_error(method(), coarsest << k, cfl))
+        .collect();
+    orders(&errors)
+}
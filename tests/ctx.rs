@@ -0,0 +1,139 @@
+mod common;
+
+use std::rc::Rc;
+
+use faer_core::{Mat, MatMut, MatRef};
+
+use conlaw::{
+    bc, cl,
+    methods::{self, Integrator, MethodOfLines},
+    ConservationLaw, Ctx, Domain, Method, Problem, Resolution, Simulation, SpatialOperator,
+};
+
+use common::run;
+
+/// Copies the solution `shift` cells upwind, exact for the advection `u_t + u_x = 0` when
+/// `Δt = shift·Δx`, with `ghost_cells` ghost cells on the left
+struct Shift {
+    shift: isize,
+    ghost_cells: usize,
+}
+
+impl Method<f64> for Shift {
+    fn left_ghost_cells(&self) -> usize {
+        self.ghost_cells
+    }
+
+    fn right_ghost_cells(&self) -> usize {
+        0
+    }
+
+    fn init(&mut self, _ctx: Ctx<f64>) {}
+
+    fn apply<'m, 'pb>(
+        &'m mut self,
+        ctx: Ctx<f64>,
+        _flux: Rc<dyn ConservationLaw<f64> + 'pb>,
+        _u: MatRef<f64>,
+        mut v: MatMut<f64>,
+    ) {
+        v.clone_from(ctx.slide(-self.shift));
+    }
+
+    fn name(&self) -> &'static str {
+        "Shift"
+    }
+
+    fn cfl_limit(&self) -> f64 {
+        self.shift as f64
+    }
+}
+
+/// Advection at the unit speed of a step at `x = 0.5` on `[0, 1]` with 100 cells, until `t = 0.3`
+/// in 10 steps, three cells long each
+fn advection() -> Problem<'static, f64> {
+    Problem::new(
+        "advection",
+        cl::Scalar::new(|u: f64| u),
+        Domain {
+            time: (0., 0.3),
+            space: (0., 1.),
+        },
+        bc::Outflow::constant(),
+        |x, mut v: MatMut<f64>| v[(0, 0)] = if x < 0.5 { 1. } else { 0. },
+    )
+}
+
+#[test]
+fn slide_beyond_two_cells() {
+    let sim = Simulation::new(advection())
+        .with_method_instance(Shift {
+            shift: 3,
+            ghost_cells: 3,
+        })
+        .with_time_resolution(Resolution::Steps(10))
+        .with_space_resolution(Resolution::Steps(100));
+    for (i, u) in run(sim).into_iter().enumerate() {
+        let exact = if i < 80 { 1. } else { 0. };
+        assert_eq!(u, exact, "u[{i}]");
+    }
+}
+
+#[test]
+#[should_panic(expected = "cannot slide by -3 cells with 2 left and 0 right ghost cells")]
+fn slide_out_of_range() {
+    let sim = Simulation::new(advection())
+        .with_method_instance(Shift {
+            shift: 3,
+            ghost_cells: 2,
+        })
+        .with_time_resolution(Resolution::Steps(10))
+        .with_space_resolution(Resolution::Steps(100));
+    run(sim);
+}
+
+/// First-order upwind operator `L(u) = -(f(u_i) - f(u_{i-1}))/Δx`, for laws moving to the right
+struct Upwind;
+
+impl SpatialOperator<f64> for Upwind {
+    fn left_ghost_cells(&self) -> usize {
+        1
+    }
+
+    fn right_ghost_cells(&self) -> usize {
+        0
+    }
+
+    fn init(&mut self, _ctx: Ctx<f64>) {}
+
+    fn rhs(&mut self, ctx: Ctx<f64>, flux: &dyn ConservationLaw<f64>, mut rhs: MatMut<f64>) {
+        let n = rhs.nrows();
+        let (mut fm, mut f) = (Mat::<f64>::zeros(n, 1), Mat::<f64>::zeros(n, 1));
+        flux.bulk_flux_function(ctx.slide(-1), fm.as_mut());
+        flux.bulk_flux_function(ctx.slide(0), f.as_mut());
+        let dx = ctx.mesh.space().delta();
+        for i in 0..n {
+            rhs.write(i, 0, (fm.read(i, 0) - f.read(i, 0)) / dx);
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Upwind"
+    }
+}
+
+#[test]
+fn spatial_operator() {
+    // integrated by the forward Euler scheme, the upwind scheme of the crate
+    let sim = || {
+        Simulation::new(advection())
+            .with_time_resolution(Resolution::Steps(100))
+            .with_space_resolution(Resolution::Steps(100))
+    };
+    let operator = MethodOfLines::new(Upwind, Integrator::ForwardEuler);
+    let u = run(sim().with_method_instance(operator));
+    let exact = run(sim().with_method::<methods::UpwindLeft<_>>());
+    for (i, (u, exact)) in u.into_iter().zip(exact).enumerate() {
+        assert!((u - exact).abs() < 1e-14, "u[{i}] = {u} instead of {exact}");
+    }
+}
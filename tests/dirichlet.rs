@@ -0,0 +1,164 @@
+use faer_core::{MatMut, MatRef};
+
+use conlaw::{
+    bc, cl, methods, Domain, Driver, Method, ObsCtx, Observer, Problem, Resolution, SimError,
+    Simulation,
+};
+
+/// Keeps a copy of the final solution
+struct Final<'a>(&'a mut Vec<f64>);
+
+impl Observer<f64> for Final<'_> {
+    fn at_cleanup(&mut self, ctx: ObsCtx<f64>) -> Result<(), SimError> {
+        let u = ctx.solution();
+        self.0.extend((0..u.nrows()).map(|i| u.read(i, 0)));
+        Ok(())
+    }
+}
+
+/// Advects a step entering through the inflow boundary at speed `a` on `[0, 1]` until `t = 0.5`
+fn inflow_advection<M: Method<f64> + Default>(a: f64) -> Vec<f64> {
+    let problem = Problem::new(
+        "inflow_advection",
+        cl::Scalar::new(move |u: f64| a * u),
+        Domain {
+            time: (0., 0.5),
+            space: (0., 1.),
+        },
+        bc::Dirichlet::new(
+            move |_, mut v| v[(0, 0)] = if a > 0. { 1. } else { 0. },
+            move |_, mut v| v[(0, 0)] = if a > 0. { 0. } else { 1. },
+        ),
+        |_, mut v| v[(0, 0)] = 0.,
+    );
+
+    let sim = Simulation::new(problem)
+        .with_method::<M>()
+        .with_time_resolution(Resolution::Steps(200))
+        .with_space_resolution(Resolution::Steps(100));
+
+    let mut solution = Vec::new();
+    Driver::new(sim)
+        .with_observer(Final(&mut solution))
+        .run()
+        .expect("failed to run simulation");
+    solution
+}
+
+fn assert_close(u: &[f64], cells: std::ops::Range<usize>, value: f64) {
+    for i in cells {
+        assert!(
+            (u[i] - value).abs() < 0.05,
+            "u[{i}] = {} instead of {value}",
+            u[i]
+        );
+    }
+}
+
+#[test]
+fn upwind_left() {
+    let u = inflow_advection::<methods::UpwindLeft<_>>(1.);
+    assert_close(&u, 0..20, 1.);
+    assert_close(&u, 80..101, 0.);
+}
+
+#[test]
+fn upwind_right() {
+    let u = inflow_advection::<methods::UpwindRight<_>>(-1.);
+    assert_close(&u, 0..20, 0.);
+    assert_close(&u, 80..101, 1.);
+}
+
+#[test]
+fn lax_friedrichs() {
+    let u = inflow_advection::<methods::LaxFriedrichs<_>>(1.);
+    assert_close(&u, 0..20, 1.);
+    assert_close(&u, 80..101, 0.);
+}
+
+#[test]
+fn mac_cormack() {
+    let u = inflow_advection::<methods::MacCormack<_>>(1.);
+    assert_close(&u, 0..20, 1.);
+    assert_close(&u, 80..101, 0.);
+}
+
+#[test]
+fn system() {
+    // two decoupled advection equations with opposite speeds
+    let problem = Problem::new(
+        "inflow_advection_system",
+        cl::General::new(2, |u: MatRef<f64>, mut v: MatMut<f64>| {
+            v[(0, 0)] = u[(0, 0)];
+            v[(1, 0)] = -u[(1, 0)];
+        }),
+        Domain {
+            time: (0., 0.5),
+            space: (0., 1.),
+        },
+        bc::Dirichlet::new(
+            |_, mut v| {
+                v[(0, 0)] = 1.;
+                v[(1, 0)] = 0.;
+            },
+            |_, mut v| {
+                v[(0, 0)] = 0.;
+                v[(1, 0)] = 2.;
+            },
+        ),
+        |_, mut v| {
+            v[(0, 0)] = 0.;
+            v[(1, 0)] = 0.;
+        },
+    );
+
+    let sim = Simulation::new(problem)
+        .with_method::<methods::LaxFriedrichs<_>>()
+        .with_time_resolution(Resolution::Steps(200))
+        .with_space_resolution(Resolution::Steps(100));
+
+    let mut solution = Vec::new();
+    Driver::new(sim)
+        .with_observer(Final(&mut solution))
+        .run()
+        .expect("failed to run simulation");
+
+    let (first, second): (Vec<_>, Vec<_>) = solution.chunks(2).map(|c| (c[0], c[1])).unzip();
+    assert_eq!(first.len(), 101);
+    assert_close(&first, 0..20, 1.);
+    assert_close(&first, 80..101, 0.);
+    assert_close(&second, 0..20, 0.);
+    assert_close(&second, 80..101, 2.);
+}
+
+#[test]
+fn time_dependent() {
+    // inflow value g(t) = t, the solution behind the front is u(x, t) = t - x
+    let problem = Problem::new(
+        "time_dependent_inflow",
+        cl::Scalar::new(|u: f64| u),
+        Domain {
+            time: (0., 0.5),
+            space: (0., 1.),
+        },
+        bc::Dirichlet::new(|t, mut v| v[(0, 0)] = t, |_, mut v| v[(0, 0)] = 0.),
+        |_, mut v| v[(0, 0)] = 0.,
+    );
+
+    let sim = Simulation::new(problem)
+        .with_method::<methods::UpwindLeft<_>>()
+        .with_time_resolution(Resolution::Steps(200))
+        .with_space_resolution(Resolution::Steps(100));
+
+    let mut solution = Vec::new();
+    Driver::new(sim)
+        .with_observer(Final(&mut solution))
+        .run()
+        .expect("failed to run simulation");
+
+    for (i, u) in solution.iter().enumerate().take(30) {
+        let exact = 0.5 - i as f64 / 100.;
+        assert!((u - exact).abs() < 0.02, "u[{i}] = {u} instead of {exact}");
+    }
+    assert_close(&solution, 70..101, 0.);
+}
@@ -0,0 +1,86 @@
+use faer_core::{MatMut, MatRef};
+
+use conlaw::{
+    bc, cl, methods, Csff1Writer, Domain, Driver, Problem, Resolution, SimError, Simulation,
+};
+
+/// Identity flux of two components advected at unit speed on `[0, 1]`
+fn advection(u0: impl Fn(f64, MatMut<f64>) + 'static) -> Problem<'static, f64> {
+    Problem::new(
+        "advection",
+        cl::General::new(2, |u: MatRef<f64>, mut v: MatMut<f64>| v.clone_from(u)),
+        Domain {
+            time: (0., 1.),
+            space: (0., 1.),
+        },
+        bc::Periodic,
+        u0,
+    )
+}
+
+#[test]
+fn nan_detected() {
+    // a NaN in the second component of the middle cell, moved to its right neighbour by upwinding
+    let problem = advection(|x, mut v| {
+        v[(0, 0)] = 1.;
+        v[(1, 0)] = if (x - 0.5).abs() < 1e-3 { f64::NAN } else { 0. };
+    });
+    let sim = Simulation::new(problem)
+        .with_method::<methods::UpwindLeft<_>>()
+        .with_time_resolution(Resolution::Steps(100))
+        .with_space_resolution(Resolution::Steps(100));
+
+    match Driver::new(sim).run() {
+        Err(SimError::Diverged {
+            iter,
+            time,
+            cell,
+            component,
+        }) => {
+            assert_eq!((iter, cell, component), (1, 50, 1));
+            assert!((time - 0.01).abs() < 1e-15);
+        }
+        other => panic!("expected a divergence, got {other:?}"),
+    }
+}
+
+#[test]
+fn bound_exceeded() {
+    // Lax-Wendroff at a CFL number of 2 amplifies the shortest waves at each step
+    let problem = advection(|x, mut v| {
+        v[(0, 0)] = (2. * std::f64::consts::PI * x).sin();
+        v[(1, 0)] = if x < 0.5 { 0. } else { 1. };
+    });
+    let sim = Simulation::new(problem)
+        .with_method::<methods::LaxWendroff<_>>()
+        .with_time_resolution(Resolution::Steps(50))
+        .with_space_resolution(Resolution::Steps(100));
+
+    let mut output = Vec::new();
+    let result = Driver::new(sim)
+        .with_time_sampling(Resolution::Steps(1))
+        .with_divergence_bound(10.)
+        .with_observer(Csff1Writer::new(&mut output))
+        .run();
+    let Err(SimError::Diverged { iter, .. }) = result else {
+        panic!("expected a divergence, got {result:?}");
+    };
+    assert!(iter > 1 && iter < 50, "diverged at step {iter}");
+
+    // the output is complete: the initial condition, one record per step before the diverged
+    // one, and the end marker
+    let name = "Lax-Wendroff (Richtmyer)";
+    let header = 5 + 1 + 5 * 4 + 4 * 8 + 4 + name.len() + 4;
+    let record = 8 * (1 + 2 * 101);
+    assert_eq!(&output[output.len() - 4..], [0xFF; 4]);
+    assert_eq!((output.len() - header - 4) % record, 0);
+    assert_eq!((output.len() - header - 4) / record, iter);
+
+    // the last record is still within the bound
+    let last = &output[output.len() - 4 - record..output.len() - 4];
+    let max = last[8..]
+        .chunks(8)
+        .map(|x| f64::from_ne_bytes(x.try_into().unwrap()).abs())
+        .fold(0., f64::max);
+    assert!(max <= 10., "largest value {max}");
+}
@@ -0,0 +1,222 @@
+mod common;
+
+use faer_core::Mat;
+
+use conlaw::{
+    bc,
+    cl::{self, EquationOfState},
+    fluxes, methods, ConservationLaw, Domain, Problem, Resolution, Simulation,
+};
+
+use common::{component, l1_error, run};
+
+/// Noble-Abel gas `p = (γ - 1)ρe/(1 - bρ)` of covolume `b`, whose pressure depends on the
+/// density: its sound speed `c² = γp/(ρ(1 - bρ))` comes from the generic formula
+#[derive(Debug, Clone, Copy)]
+struct NobleAbel {
+    gamma: f64,
+    covolume: f64,
+}
+
+impl EquationOfState<f64> for NobleAbel {
+    fn pressure(&self, density: f64, internal_energy: f64) -> f64 {
+        (self.gamma - 1.) * internal_energy / (1. - self.covolume * density)
+    }
+
+    fn internal_energy(&self, density: f64, pressure: f64) -> f64 {
+        pressure * (1. - self.covolume * density) / (self.gamma - 1.)
+    }
+
+    fn pressure_derivatives(&self, density: f64, internal_energy: f64) -> (f64, f64) {
+        let volume = 1. - self.covolume * density;
+        (
+            (self.gamma - 1.) * internal_energy * self.covolume / (volume * volume),
+            (self.gamma - 1.) / volume,
+        )
+    }
+}
+
+const NOBLE_ABEL: NobleAbel = NobleAbel {
+    gamma: 1.4,
+    covolume: 0.2,
+};
+
+/// `(ρ, p)` states at which the equations of state are checked
+const STATES: [(f64, f64); 4] = [(1., 1.), (0.125, 0.1), (2., 0.5), (0.8, 3.)];
+
+/// Checks the equation of state `eos` against itself: the internal energy inverts the pressure,
+/// the derivatives are those of the pressure, and the sound speed is the generic one
+fn assert_consistent(eos: &dyn EquationOfState<f64>) {
+    for (rho, p) in STATES {
+        let e = eos.internal_energy(rho, p);
+        assert!((eos.pressure(rho, e) - p).abs() < 1e-12, "at {rho}, {p}");
+
+        let (p_rho, p_e) = eos.pressure_derivatives(rho, e);
+        let h = 1e-6;
+        let d_rho = (eos.pressure(rho + h, e) - eos.pressure(rho - h, e)) / (2. * h);
+        let d_e = (eos.pressure(rho, e + h) - eos.pressure(rho, e - h)) / (2. * h);
+        assert!(
+            (p_rho - d_rho).abs() < 1e-6,
+            "∂p/∂ρ {p_rho} instead of {d_rho}"
+        );
+        assert!((p_e - d_e).abs() < 1e-6, "∂p/∂(ρe) {p_e} instead of {d_e}");
+
+        let c = (p_rho + p_e * (e + p) / rho).sqrt();
+        let sound_speed = eos.sound_speed(rho, p);
+        assert!(
+            (sound_speed - c).abs() < 1e-12,
+            "c {sound_speed} instead of {c}"
+        );
+    }
+}
+
+#[test]
+fn equations_of_state() {
+    let ideal = cl::IdealGas::<f64>::default();
+    let stiffened = cl::StiffenedGas::new(4.4, 6.);
+    for eos in [
+        &ideal as &dyn EquationOfState<f64>,
+        &cl::IdealGas::new(5. / 3.),
+        &stiffened,
+        &NOBLE_ABEL,
+    ] {
+        assert_consistent(eos);
+    }
+
+    for (rho, p) in STATES {
+        // the closed forms of the overridden sound speeds
+        let c = (1.4 * p / rho).sqrt();
+        assert!((ideal.sound_speed(rho, p) - c).abs() < 1e-12);
+        let c = (4.4 * (p + 6.) / rho).sqrt();
+        assert!((stiffened.sound_speed(rho, p) - c).abs() < 1e-12);
+        let c = (1.4 * p / (rho * (1. - 0.2 * rho))).sqrt();
+        assert!((NOBLE_ABEL.sound_speed(rho, p) - c).abs() < 1e-12);
+
+        // a stiffened gas without stiffening is an ideal gas
+        let e = ideal.internal_energy(rho, p);
+        let gas = cl::StiffenedGas::new(1.4, 0.);
+        assert_eq!(gas.internal_energy(rho, p), e);
+        assert_eq!(gas.pressure(rho, e), ideal.pressure(rho, e));
+    }
+}
+
+/// Checks the primitive variables, the flux and the eigen-structure of `euler` at `w`
+fn assert_euler<E: EquationOfState<f64>>(euler: &cl::Euler<f64, E>, w: cl::Primitive<f64>) {
+    let mut u = Mat::<f64>::zeros(3, 1);
+    euler.conserved(&w, u.as_mut());
+    let back = euler.primitive(u.as_ref());
+    assert!((back.density - w.density).abs() < 1e-12);
+    assert!((back.velocity - w.velocity).abs() < 1e-12);
+    assert!((back.pressure - w.pressure).abs() < 1e-12);
+    let c = euler.eos().sound_speed(w.density, w.pressure);
+    assert!((euler.sound_speed(u.as_ref()) - c).abs() < 1e-12);
+
+    // the Jacobian is the derivative of the flux
+    let mut jac = Mat::<f64>::zeros(3, 3);
+    euler.jacobian(u.as_ref(), jac.as_mut());
+    let h = 1e-6;
+    for j in 0..3 {
+        let (mut plus, mut minus) = (u.clone(), u.clone());
+        plus.write(j, 0, u.read(j, 0) + h);
+        minus.write(j, 0, u.read(j, 0) - h);
+        let (mut fp, mut fm) = (Mat::<f64>::zeros(3, 1), Mat::<f64>::zeros(3, 1));
+        euler.flux_function(plus.as_ref(), fp.as_mut());
+        euler.flux_function(minus.as_ref(), fm.as_mut());
+        for i in 0..3 {
+            let derivative = (fp.read(i, 0) - fm.read(i, 0)) / (2. * h);
+            assert!(
+                (jac.read(i, j) - derivative).abs() < 1e-6,
+                "A[{i}, {j}] = {} instead of {derivative}",
+                jac.read(i, j)
+            );
+        }
+    }
+
+    // A R = R Λ and L R = I, with the eigenvalues u - c, u, u + c
+    let eigen = euler.eigen_decomposition(u.as_ref()).unwrap();
+    let speeds = [w.velocity - c, w.velocity, w.velocity + c];
+    for (k, speed) in speeds.into_iter().enumerate() {
+        assert!((eigen.values.read(k, 0) - speed).abs() < 1e-12);
+    }
+    let (ar, identity) = (&jac * &eigen.right, &eigen.left * &eigen.right);
+    for i in 0..3 {
+        for (j, speed) in speeds.into_iter().enumerate() {
+            let rl = eigen.right.read(i, j) * speed;
+            assert!((ar.read(i, j) - rl).abs() < 1e-10, "A R at ({i}, {j})");
+            let expected = if i == j { 1. } else { 0. };
+            assert!(
+                (identity.read(i, j) - expected).abs() < 1e-10,
+                "L R at ({i}, {j})"
+            );
+        }
+    }
+}
+
+#[test]
+fn euler() {
+    let states = STATES.map(|(density, pressure)| cl::Primitive {
+        density,
+        velocity: 0.3 - density,
+        pressure,
+    });
+    for w in states {
+        assert_euler(&cl::Euler::<f64>::default(), w);
+        assert_euler(&cl::Euler::from_eos(cl::StiffenedGas::new(4.4, 6.)), w);
+        assert_euler(&cl::Euler::from_eos(NOBLE_ABEL), w);
+    }
+}
+
+#[test]
+fn stiffened_gas_shock_tube() {
+    // a water shock tube, far from an ideal gas since p∞ is much larger than the pressures
+    let water = cl::Euler::from_eos(cl::StiffenedGas::new(4.4, 6.));
+    let (left, right) = (
+        cl::Primitive {
+            density: 1.,
+            velocity: 0.,
+            pressure: 2.,
+        },
+        cl::Primitive {
+            density: 0.9,
+            velocity: 0.,
+            pressure: 0.5,
+        },
+    );
+    let problem = Problem::new(
+        "water",
+        water,
+        Domain {
+            time: (0., 0.05),
+            space: (0., 1.),
+        },
+        bc::Outflow::constant(),
+        move |x, v| water.conserved(if x < 0.5 { &left } else { &right }, v),
+    );
+    let sim = Simulation::new(problem)
+        .with_method_instance(methods::FiniteVolume::new(fluxes::Hll))
+        .with_time_resolution(Resolution::Steps(100))
+        .with_space_resolution(Resolution::Steps(200));
+    let u = run(sim);
+
+    // against the exact solution of the stiffened gas, not that of an ideal gas whose waves are
+    // more than three times slower
+    let density = component(&u, 3, 0);
+    let exact = |solution: cl::EulerRiemannSolution<f64>| -> Vec<f64> {
+        (0..=200)
+            .map(|i| solution.sample((i as f64 / 200. - 0.5) / 0.05).density)
+            .collect()
+    };
+    let error = l1_error(&density, &exact(water.riemann(&left, &right)));
+    assert!(error < 5e-3, "error {error}");
+    let ideal = l1_error(
+        &density,
+        &exact(cl::Euler::default().riemann(&left, &right)),
+    );
+    assert!(ideal > 3. * error, "error {ideal} against an ideal gas");
+
+    // the pressure stays within its initial bounds, where an ideal gas would not be defined
+    for cell in u.chunks(3) {
+        let p = water.pressure(Mat::from_fn(3, 1, |i, _| cell[i]).as_ref());
+        assert!((0.5 - 1e-6..=2. + 1e-6).contains(&p), "pressure {p}");
+    }
+}
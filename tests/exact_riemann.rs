@@ -0,0 +1,243 @@
+mod common;
+
+use faer_core::Mat;
+
+use conlaw::{
+    bc, cl, fluxes, methods,
+    riemann::{ExactRiemannSolution, Wave},
+    Domain, Problem, Resolution, Simulation,
+};
+
+use common::{component, l1_error, run};
+
+fn assert_near(value: f64, exact: f64, tolerance: f64, what: &str) {
+    assert!(
+        (value - exact).abs() < tolerance,
+        "{what} {value} instead of {exact}"
+    );
+}
+
+fn primitive(density: f64, velocity: f64, pressure: f64) -> cl::Primitive<f64> {
+    cl::Primitive {
+        density,
+        velocity,
+        pressure,
+    }
+}
+
+fn flow(depth: f64, velocity: f64) -> cl::Flow<f64> {
+    cl::Flow { depth, velocity }
+}
+
+#[test]
+fn sod() {
+    // star region and waves of Sod's problem, after Toro's tables
+    let euler = cl::Euler::<f64>::default();
+    let solution = euler.riemann(&primitive(1., 0., 1.), &primitive(0.125, 0., 0.1));
+    let star = solution.star.expect("no vacuum");
+    assert_near(star.pressure, 0.30313, 1e-5, "star pressure");
+    assert_near(star.velocity, 0.92745, 1e-5, "star velocity");
+    assert_near(star.left_density, 0.42632, 1e-5, "left star density");
+    assert_near(star.right_density, 0.26557, 1e-5, "right star density");
+    let Wave::Rarefaction { head, tail } = solution.left_wave else {
+        panic!("left shock");
+    };
+    assert_near(head, -1.18322, 1e-5, "head of the rarefaction");
+    assert_near(tail, -0.07027, 1e-5, "tail of the rarefaction");
+    let Wave::Shock { speed } = solution.right_wave else {
+        panic!("right rarefaction");
+    };
+    assert_near(speed, 1.75216, 1e-5, "shock speed");
+
+    // sampled states, the right one being linked to the star state by the Rankine-Hugoniot
+    // conditions
+    assert_eq!(solution.sample(-2.), primitive(1., 0., 1.));
+    assert_eq!(solution.sample(2.), primitive(0.125, 0., 0.1));
+    let (behind, ahead) = (solution.sample(speed - 1e-9), solution.sample(speed + 1e-9));
+    let mut u = [Mat::<f64>::zeros(3, 1), Mat::<f64>::zeros(3, 1)];
+    let mut f = [Mat::<f64>::zeros(3, 1), Mat::<f64>::zeros(3, 1)];
+    for (k, w) in [behind, ahead].iter().enumerate() {
+        euler.conserved(w, u[k].as_mut());
+        conlaw::ConservationLaw::flux_function(&euler, u[k].as_ref(), f[k].as_mut());
+    }
+    for i in 0..3 {
+        let jump = f[0].read(i, 0) - f[1].read(i, 0);
+        let exact = speed * (u[0].read(i, 0) - u[1].read(i, 0));
+        assert_near(jump, exact, 1e-10, "flux jump");
+    }
+
+    // the fan is continuous, isentropic, and its characteristic u - c is ξ
+    for xi in [-1.1, -0.6, -0.1] {
+        let w = solution.sample(xi);
+        let c = (1.4 * w.pressure / w.density).sqrt();
+        assert_near(w.velocity - c, xi, 1e-12, "characteristic speed");
+        assert_near(w.pressure / w.density.powf(1.4), 1., 1e-12, "entropy");
+    }
+}
+
+#[test]
+fn vacuum() {
+    let euler = cl::Euler::<f64>::default();
+
+    // expanding into a vacuum: the gas escapes at u + 2c/(γ - 1)
+    let (gas, empty) = (primitive(1., 0., 1.), primitive(0., 0., 0.));
+    let solution = euler.riemann(&gas, &empty);
+    assert!(solution.star.is_none());
+    let escape = 2. * 1.4f64.sqrt() / 0.4;
+    assert_near(
+        solution.left_wave.inner_speed(),
+        escape,
+        1e-12,
+        "escape speed",
+    );
+    assert_eq!(solution.sample(escape + 0.1), empty);
+
+    // from the conserved variables, the vacuum being at rest instead of having the velocity 0/0
+    let (mut left, right) = (Mat::<f64>::zeros(3, 1), Mat::<f64>::zeros(3, 1));
+    euler.conserved(&gas, left.as_mut());
+    assert_eq!(euler.primitive(right.as_ref()), empty);
+    let mut u = Mat::<f64>::zeros(3, 1);
+    for xi in [-2., 0., 2., 5.] {
+        euler.riemann_sample(left.as_ref(), right.as_ref(), xi, u.as_mut());
+        let w = euler.primitive(u.as_ref());
+        assert!(w.density.is_finite() && w.velocity.is_finite() && w.pressure.is_finite());
+    }
+
+    // and the other way around
+    euler.riemann_sample(right.as_ref(), left.as_ref(), -escape - 0.1, u.as_mut());
+    assert_eq!((u.read(0, 0), u.read(1, 0), u.read(2, 0)), (0., 0., 0.));
+
+    // a vacuum created between two receding gases
+    let solution = euler.riemann(&primitive(1., -4., 0.4), &primitive(1., 4., 0.4));
+    assert!(solution.star.is_none());
+    assert_eq!(solution.sample(0.).density, 0.);
+}
+
+#[test]
+fn stoker_and_ritter() {
+    let g: f64 = 9.81;
+    let sw = cl::ShallowWater::new(g);
+
+    // wet dam break: the star depth equates the velocity behind the left rarefaction to the one
+    // behind the right shock, found here by bisection
+    let (hl, hr) = (1., 0.5);
+    let behind_rarefaction = |h: f64| 2. * ((g * hl).sqrt() - (g * h).sqrt());
+    let behind_shock = |h: f64| (h - hr) * (0.5 * g * (h + hr) / (h * hr)).sqrt();
+    let (mut lo, mut hi) = (hr, hl);
+    for _ in 0..60 {
+        let h = 0.5 * (lo + hi);
+        if behind_rarefaction(h) > behind_shock(h) {
+            lo = h;
+        } else {
+            hi = h;
+        }
+    }
+    let solution = sw.riemann(&flow(hl, 0.), &flow(hr, 0.));
+    let star = solution.star.expect("wet");
+    assert_near(star.depth, lo, 1e-12, "star depth");
+    assert_near(star.velocity, behind_shock(lo), 1e-12, "star velocity");
+    let Wave::Shock { speed } = solution.right_wave else {
+        panic!("right rarefaction");
+    };
+    // mass conservation across the shock
+    assert_near(
+        speed * (star.depth - hr),
+        star.depth * star.velocity,
+        1e-12,
+        "mass",
+    );
+
+    // dry dam break: Ritter's parabola h = (2√(gh₀) - ξ)²/(9g), between -√(gh₀) and 2√(gh₀)
+    let solution = sw.riemann(&flow(1., 0.), &flow(0., 0.));
+    assert!(solution.star.is_none());
+    let c = g.sqrt();
+    for xi in [-4., -c, -1., 0., 2., 5., 2. * c, 7.] {
+        let w = solution.sample(xi);
+        let (depth, velocity) = if xi < -c {
+            (1., 0.)
+        } else if xi < 2. * c {
+            ((2. * c - xi).powi(2) / (9. * g), 2. * (xi + c) / 3.)
+        } else {
+            (0., 0.)
+        };
+        assert_near(w.depth, depth, 1e-12, "depth");
+        assert_near(w.velocity, velocity, 1e-12, "velocity");
+    }
+
+    // dry states from the conserved variables are at rest
+    let mut u = Mat::<f64>::zeros(2, 1);
+    let (left, right) = (
+        Mat::from_fn(2, 1, |i, _| [1., 0.][i]),
+        Mat::<f64>::zeros(2, 1),
+    );
+    sw.riemann_sample(left.as_ref(), right.as_ref(), 0., u.as_mut());
+    assert_near(u.read(0, 0), 4. / 9., 1e-12, "depth at the dam");
+}
+
+#[test]
+fn single_precision() {
+    // the exact solutions in single precision
+    let euler = cl::Euler::<f32>::default();
+    let left = cl::Primitive {
+        density: 1f32,
+        velocity: 0.,
+        pressure: 1.,
+    };
+    let right = cl::Primitive {
+        density: 0.125f32,
+        velocity: 0.,
+        pressure: 0.1,
+    };
+    let star = euler.riemann(&left, &right).star.expect("no vacuum");
+    assert!((star.pressure - 0.30313).abs() < 1e-5);
+
+    let sw = cl::ShallowWater::<f32>::new(9.81);
+    let solution = sw.riemann(
+        &cl::Flow {
+            depth: 1.,
+            velocity: 0.,
+        },
+        &cl::Flow {
+            depth: 0.,
+            velocity: 0.,
+        },
+    );
+    assert!((solution.sample(0.).depth - 4. / 9.).abs() < 1e-6);
+}
+
+#[test]
+fn dam_break_convergence() {
+    // the HLL scheme converges to the exact solution of a wet dam break
+    let sw = cl::ShallowWater::new(1.);
+    let (left, right) = (flow(1., 0.), flow(0.2, 0.));
+    let errors: Vec<f64> = [50, 100, 200]
+        .into_iter()
+        .map(|n| {
+            let problem = Problem::new(
+                "dam_break",
+                sw,
+                Domain {
+                    time: (0., 0.25),
+                    space: (0., 1.),
+                },
+                bc::Outflow::constant(),
+                move |x, v| sw.conserved(if x < 0.5 { &left } else { &right }, v),
+            );
+            let sim = Simulation::new(problem)
+                .with_method_instance(methods::FiniteVolume::new(fluxes::Hll))
+                .with_time_resolution(Resolution::Steps(n / 2))
+                .with_space_resolution(Resolution::Steps(n));
+            let depth = component(&run(sim), 2, 0);
+            let solution = sw.riemann(&left, &right);
+            let exact: Vec<f64> = (0..=n)
+                .map(|i| solution.sample((i as f64 / n as f64 - 0.5) / 0.25).depth)
+                .collect();
+            l1_error(&depth, &exact)
+        })
+        .collect();
+    assert!(errors[2] < 1e-2, "errors {errors:?}");
+    assert!(
+        errors.windows(2).all(|e| e[1] < 0.8 * e[0]),
+        "errors {errors:?}"
+    );
+}
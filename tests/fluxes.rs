@@ -0,0 +1,172 @@
+mod common;
+
+use faer_core::Mat;
+
+use conlaw::{
+    bc, cl, fluxes, fluxes::NumericalFlux, methods, reference::EntropySolution, ConservationLaw,
+    Domain, Grid, Problem, Resolution, Simulation,
+};
+
+use common::{advection_orders, l1_error, run};
+
+fn burgers() -> cl::Scalar<f64, impl Fn(f64) -> f64> {
+    cl::Scalar::new(|u: f64| 0.5 * u * u)
+}
+
+/// Numerical flux of `nf` between two cells of a scalar law
+fn scalar_flux(
+    nf: &dyn NumericalFlux<f64>,
+    cl: &dyn ConservationLaw<f64>,
+    ul: f64,
+    ur: f64,
+) -> f64 {
+    let (left, right) = (Mat::from_fn(1, 1, |_, _| ul), Mat::from_fn(1, 1, |_, _| ur));
+    let mut flux = Mat::<f64>::zeros(1, 1);
+    nf.flux(cl, left.as_ref(), right.as_ref(), flux.as_mut());
+    flux.read(0, 0)
+}
+
+fn assert_flux(
+    nf: &dyn NumericalFlux<f64>,
+    cl: &dyn ConservationLaw<f64>,
+    ul: f64,
+    ur: f64,
+    exact: f64,
+) {
+    let f = scalar_flux(nf, cl, ul, ur);
+    assert!(
+        (f - exact).abs() < 1e-9,
+        "{} flux {f} instead of {exact} between {ul} and {ur}",
+        nf.name()
+    );
+}
+
+const FLUXES: [&dyn NumericalFlux<f64>; 3] =
+    [&fluxes::Rusanov, &fluxes::Hll, &fluxes::EngquistOsher];
+
+#[test]
+fn consistency() {
+    let burgers = burgers();
+    for nf in FLUXES {
+        for u in [-2., -0.5, 0., 1., 3.] {
+            assert_flux(nf, &burgers, u, u, 0.5 * u * u);
+        }
+    }
+
+    // a system: the Euler equations
+    let euler = cl::Euler::<f64>::default();
+    let u = Mat::from_fn(3, 1, |i, _| [1., 0.5, 2.5][i]);
+    let mut exact = Mat::<f64>::zeros(3, 1);
+    euler.flux_function(u.as_ref(), exact.as_mut());
+    for nf in [&fluxes::Rusanov as &dyn NumericalFlux<f64>, &fluxes::Hll] {
+        let mut flux = Mat::<f64>::zeros(3, 1);
+        nf.flux(&euler, u.as_ref(), u.as_ref(), flux.as_mut());
+        for i in 0..3 {
+            assert!((flux.read(i, 0) - exact.read(i, 0)).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn upwind_on_linear_advection() {
+    for a in [2., -2.] {
+        let advection = cl::Scalar::new(move |u: f64| a * u);
+        for nf in FLUXES {
+            let upwind = if a > 0. { a * 1. } else { a * 3. };
+            assert_flux(nf, &advection, 1., 3., upwind);
+        }
+    }
+}
+
+#[test]
+fn burgers_values() {
+    let burgers = burgers();
+    // Rusanov: (f(u_L) + f(u_R))/2 - max|u| (u_R - u_L)/2
+    assert_flux(&fluxes::Rusanov, &burgers, 1., 0., 0.75);
+    assert_flux(&fluxes::Rusanov, &burgers, -1., 1., -0.5);
+    // HLL: upwind when both wave speed estimates have the same sign, otherwise
+    // (s_R f(u_L) - s_L f(u_R) + s_L s_R (u_R - u_L)) / (s_R - s_L)
+    assert_flux(&fluxes::Hll, &burgers, 1., 0., 0.5);
+    assert_flux(&fluxes::Hll, &burgers, -1., 1., -0.5);
+    assert_flux(
+        &fluxes::Hll,
+        &burgers,
+        -1.,
+        2.,
+        (2. * 0.5 + 2. - 2. * 3.) / 3.,
+    );
+    // Engquist-Osher: f⁺(u_L) + f⁻(u_R), with f⁺ = f on [0, ∞) and f⁻ = f on (-∞, 0]
+    assert_flux(&fluxes::EngquistOsher, &burgers, 1., 0., 0.5);
+    assert_flux(&fluxes::EngquistOsher, &burgers, -1., 1., 0.);
+    assert_flux(&fluxes::EngquistOsher, &burgers, 1., -1., 1.);
+    assert_flux(&fluxes::EngquistOsher, &burgers, -2., -1., 0.5);
+}
+
+#[test]
+fn first_order() {
+    for orders in [
+        advection_orders(
+            || methods::FiniteVolume::<f64, _>::new(fluxes::Rusanov),
+            25,
+            0.5,
+        ),
+        advection_orders(
+            || methods::FiniteVolume::<f64, _>::new(fluxes::Hll),
+            25,
+            0.5,
+        ),
+        advection_orders(
+            || methods::FiniteVolume::<f64, _>::new(fluxes::EngquistOsher),
+            25,
+            0.5,
+        ),
+    ] {
+        assert!(orders.iter().all(|&p| p > 0.8), "orders {orders:?}");
+    }
+}
+
+/// L1 error of the finite volume scheme of `nf` on a Burgers Riemann problem until `t = 0.25`
+fn burgers_riemann_error<NF: NumericalFlux<f64>>(nf: NF, ul: f64, ur: f64) -> f64 {
+    let problem = Problem::new(
+        "burgers_riemann",
+        burgers(),
+        Domain {
+            time: (0., 0.25),
+            space: (0., 1.),
+        },
+        bc::Outflow::constant(),
+        move |x, mut v| v[(0, 0)] = if x < 0.5 { ul } else { ur },
+    );
+    let sim = Simulation::new(problem)
+        .with_method_instance(methods::FiniteVolume::new(nf))
+        .with_time_resolution(Resolution::Steps(100))
+        .with_space_resolution(Resolution::Steps(200));
+    let u = run(sim);
+
+    let exact = EntropySolution::piecewise_constant(burgers(), &[0.5], &[ul, ur])
+        .eval_on(Grid::from_steps(0., 1., 200), 0.25);
+    l1_error(&u, &exact)
+}
+
+#[test]
+fn burgers_riemann_problems() {
+    // a shock and a transonic rarefaction, entropy-satisfying for all fluxes
+    for (ul, ur) in [(2., 1.), (-1., 1.)] {
+        for error in [
+            burgers_riemann_error(fluxes::Rusanov, ul, ur),
+            burgers_riemann_error(fluxes::Hll, ul, ur),
+            burgers_riemann_error(fluxes::EngquistOsher, ul, ur),
+        ] {
+            assert!(error < 3e-2, "error {error} between {ul} and {ur}");
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "scalar conservation law")]
+fn engquist_osher_on_a_system() {
+    let euler = cl::Euler::<f64>::default();
+    let u = Mat::from_fn(3, 1, |i, _| [1., 0.5, 2.5][i]);
+    let mut flux = Mat::<f64>::zeros(3, 1);
+    fluxes::EngquistOsher.flux(&euler, u.as_ref(), u.as_ref(), flux.as_mut());
+}
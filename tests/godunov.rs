@@ -0,0 +1,118 @@
+mod common;
+
+use faer_core::{Mat, MatMut, MatRef};
+
+use conlaw::{
+    bc, cl, fluxes, methods, reference::EntropySolution, riemann::ExactConvexScalar,
+    riemann::RiemannSolver, Domain, Grid, Method, Problem, Resolution, Simulation,
+};
+
+use common::{l1_error, run};
+
+fn burgers() -> cl::Scalar<f64, impl Fn(f64) -> f64> {
+    cl::Scalar::new(|u: f64| 0.5 * u * u)
+}
+
+fn interface_flux(cl: &dyn conlaw::ConservationLaw<f64>, ul: f64, ur: f64) -> f64 {
+    let (left, right) = (Mat::from_fn(1, 1, |_, _| ul), Mat::from_fn(1, 1, |_, _| ur));
+    let mut flux = Mat::<f64>::zeros(1, 1);
+    ExactConvexScalar.interface_flux(cl, left.as_ref(), right.as_ref(), flux.as_mut());
+    flux.read(0, 0)
+}
+
+#[test]
+fn exact_convex_scalar() {
+    let burgers = burgers();
+    let cases = [
+        // shocks moving right, left, and standing still
+        (1., 0., 0.5),
+        (-1., -2., 2.),
+        (1., -1., 0.5),
+        // rarefactions moving right, left, and transonic through the sonic point 0
+        (0., 1., 0.),
+        (-2., -1., 0.5),
+        (-1., 2., 0.),
+    ];
+    for (ul, ur, exact) in cases {
+        let f = interface_flux(&burgers, ul, ur);
+        assert!(
+            (f - exact).abs() < 1e-9,
+            "flux {f} instead of {exact} between {ul} and {ur}"
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "needs a scalar conservation law")]
+fn system() {
+    let law = cl::General::new(2, |u: MatRef<f64>, mut v: MatMut<f64>| v.clone_from(u));
+    let (left, right) = (Mat::<f64>::zeros(2, 1), Mat::<f64>::zeros(2, 1));
+    let mut flux = Mat::<f64>::zeros(2, 1);
+    ExactConvexScalar.interface_flux(&law, left.as_ref(), right.as_ref(), flux.as_mut());
+}
+
+#[test]
+#[should_panic(expected = "not convex")]
+fn concave_flux() {
+    interface_flux(&cl::Scalar::new(|u: f64| -0.5 * u * u), 0., 1.);
+}
+
+/// Godunov's scheme on Burgers' equation from `ul` on the left of `x = 0.5` and `ur` on its
+/// right, until `t = 0.25`, with its L1 error against the entropy solution
+fn riemann_problem<M: Method<f64>>(method: M, ul: f64, ur: f64) -> (Vec<f64>, f64) {
+    let problem = Problem::new(
+        "burgers_riemann",
+        burgers(),
+        Domain {
+            time: (0., 0.25),
+            space: (0., 1.),
+        },
+        bc::Outflow::constant(),
+        move |x, mut v| v[(0, 0)] = if x < 0.5 { ul } else { ur },
+    );
+    let sim = Simulation::new(problem)
+        .with_method_instance(method)
+        .with_time_resolution(Resolution::Steps(100))
+        .with_space_resolution(Resolution::Steps(200));
+    let u = run(sim);
+
+    let exact = EntropySolution::piecewise_constant(burgers(), &[0.5], &[ul, ur])
+        .eval_on(Grid::from_steps(0., 1., 200), 0.25);
+    let error = l1_error(&u, &exact);
+    (u, error)
+}
+
+#[test]
+fn shock() {
+    let (u, error) = riemann_problem(methods::Godunov::new(ExactConvexScalar), 2., 1.);
+    assert!(error < 5e-3, "error {error}");
+    // the shock, at speed 1.5, has left the initial discontinuity behind
+    assert!((u[120] - 2.).abs() < 1e-12 && (u[200] - 1.).abs() < 1e-12);
+}
+
+#[test]
+fn transonic_rarefaction() {
+    let (u, error) = riemann_problem(methods::Godunov::new(ExactConvexScalar), -1., 1.);
+    assert!(error < 2e-2, "error {error}");
+    // no entropy-violating expansion shock at the sonic point
+    assert!(u[100].abs() < 0.05, "u = {} at the sonic point", u[100]);
+}
+
+#[test]
+fn finite_volume_flux() {
+    // source-compatible with the finite volume scheme of the Godunov flux, under its own name
+    let godunov = methods::Godunov::<f64, ExactConvexScalar>::default();
+    assert_eq!(godunov.name(), "Godunov");
+    assert_eq!(
+        (godunov.left_ghost_cells(), godunov.right_ghost_cells()),
+        (1, 1)
+    );
+
+    let (u, _) = riemann_problem(godunov, -1., 2.);
+    let (v, _) = riemann_problem(
+        methods::FiniteVolume::new(fluxes::Godunov(ExactConvexScalar)),
+        -1.,
+        2.,
+    );
+    assert_eq!(u, v);
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use faer_core::{Mat, MatMut, MatRef};
+
+use conlaw::{
+    bc, cl,
+    fluxes::{self, NumericalFlux},
+    methods, ConservationLaw, Domain, Problem, Resolution, Simulation,
+};
+
+use common::{component, l1_error, run};
+
+fn conserved<E: cl::EquationOfState<f64>>(
+    euler: &cl::Euler<f64, E>,
+    density: f64,
+    velocity: f64,
+    pressure: f64,
+) -> Mat<f64> {
+    let mut u = Mat::<f64>::zeros(3, 1);
+    euler.conserved(
+        &cl::Primitive {
+            density,
+            velocity,
+            pressure,
+        },
+        u.as_mut(),
+    );
+    u
+}
+
+fn hllc_flux(cl: &dyn ConservationLaw<f64>, left: &Mat<f64>, right: &Mat<f64>) -> Mat<f64> {
+    let mut flux = Mat::<f64>::zeros(3, 1);
+    fluxes::Hllc.flux(cl, left.as_ref(), right.as_ref(), flux.as_mut());
+    flux
+}
+
+fn assert_flux(flux: &Mat<f64>, exact: [f64; 3]) {
+    for (i, exact) in exact.into_iter().enumerate() {
+        assert!(
+            (flux.read(i, 0) - exact).abs() < 1e-12 * (1. + exact.abs()),
+            "flux component {i}: {} instead of {exact}",
+            flux.read(i, 0)
+        );
+    }
+}
+
+fn exact_flux(cl: &dyn ConservationLaw<f64>, u: &Mat<f64>) -> [f64; 3] {
+    let mut f = Mat::<f64>::zeros(3, 1);
+    cl.flux_function(u.as_ref(), f.as_mut());
+    [f.read(0, 0), f.read(1, 0), f.read(2, 0)]
+}
+
+#[test]
+fn equation_of_state_of_the_law() {
+    // consistency, and exact moving and stationary contacts, with the equation of state of the
+    // law rather than an ideal gas of γ = 1.4
+    let ideal = cl::Euler::<f64>::new(5. / 3.);
+    let stiffened = cl::Euler::from_eos(cl::StiffenedGas::new(4.4, 6.));
+    for (law, u, contact) in [
+        (
+            &ideal as &dyn ConservationLaw<f64>,
+            conserved(&ideal, 1., 0.5, 2.),
+            conserved(&ideal, 0.3, 0.5, 2.),
+        ),
+        (
+            &stiffened,
+            conserved(&stiffened, 1., 0.5, 2.),
+            conserved(&stiffened, 0.3, 0.5, 2.),
+        ),
+    ] {
+        assert_flux(&hllc_flux(law, &u, &u), exact_flux(law, &u));
+        assert_flux(&hllc_flux(law, &u, &contact), exact_flux(law, &u));
+    }
+
+    let (left, right) = (
+        conserved(&stiffened, 1., 0., 2.),
+        conserved(&stiffened, 0.3, 0., 2.),
+    );
+    assert_flux(&hllc_flux(&stiffened, &left, &right), [0., 2., 0.]);
+}
+
+#[test]
+fn supersonic() {
+    // upwind when all waves run in the same direction
+    let euler = cl::Euler::<f64>::default();
+    let (left, right) = (
+        conserved(&euler, 1., 3., 1.),
+        conserved(&euler, 0.5, 2.5, 0.4),
+    );
+    assert_flux(&hllc_flux(&euler, &left, &right), exact_flux(&euler, &left));
+    let (left, right) = (
+        conserved(&euler, 1., -3., 1.),
+        conserved(&euler, 0.5, -2.5, 0.4),
+    );
+    assert_flux(
+        &hllc_flux(&euler, &left, &right),
+        exact_flux(&euler, &right),
+    );
+}
+
+#[test]
+#[should_panic(expected = "needs the Euler equations")]
+fn not_euler() {
+    let law = cl::General::new(2, |u: MatRef<f64>, mut v: MatMut<f64>| v.clone_from(u));
+    let (left, right) = (Mat::<f64>::zeros(2, 1), Mat::<f64>::zeros(2, 1));
+    let mut flux = Mat::<f64>::zeros(2, 1);
+    fluxes::Hllc.flux(&law, left.as_ref(), right.as_ref(), flux.as_mut());
+}
+
+#[test]
+fn vacuum() {
+    let euler = cl::Euler::<f64>::default();
+    let (gas, empty) = (conserved(&euler, 1., 0., 1.), Mat::<f64>::zeros(3, 1));
+    assert_flux(&hllc_flux(&euler, &empty, &empty), [0., 0., 0.]);
+
+    // the gas flows into the vacuum, symmetrically on both sides
+    let (right, left) = (
+        hllc_flux(&euler, &gas, &empty),
+        hllc_flux(&euler, &empty, &gas),
+    );
+    assert!(right.read(0, 0) > 0., "mass flux {}", right.read(0, 0));
+    assert_flux(
+        &left,
+        [-right.read(0, 0), right.read(1, 0), -right.read(2, 0)],
+    );
+}
+
+/// L1 error of the density of the finite volume scheme of `nf` on Sod's shock tube
+fn sod_error<NF: NumericalFlux<f64>>(nf: NF) -> f64 {
+    let euler = cl::Euler::<f64>::default();
+    let (left, right) = (
+        cl::Primitive {
+            density: 1.,
+            velocity: 0.,
+            pressure: 1.,
+        },
+        cl::Primitive {
+            density: 0.125,
+            velocity: 0.,
+            pressure: 0.1,
+        },
+    );
+    let problem = Problem::new(
+        "sod",
+        euler,
+        Domain {
+            time: (0., 0.2),
+            space: (0., 1.),
+        },
+        bc::Outflow::constant(),
+        move |x, v| euler.conserved(if x < 0.5 { &left } else { &right }, v),
+    );
+    let sim = Simulation::new(problem)
+        .with_method_instance(methods::FiniteVolume::new(nf))
+        .with_time_resolution(Resolution::Steps(200))
+        .with_space_resolution(Resolution::Steps(200));
+
+    let density = component(&run(sim), 3, 0);
+    let solution = euler.riemann(&left, &right);
+    let exact: Vec<f64> = (0..=200)
+        .map(|i| solution.sample((i as f64 / 200. - 0.5) / 0.2).density)
+        .collect();
+    l1_error(&density, &exact)
+}
+
+#[test]
+fn sod() {
+    let (hllc, hll) = (sod_error(fluxes::Hllc), sod_error(fluxes::Hll));
+    assert!(hllc < 2e-2, "error {hllc}");
+    // the contact is sharper than with HLL
+    assert!(hllc < hll, "error {hllc}, {hll} with HLL");
+}
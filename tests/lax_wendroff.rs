@@ -0,0 +1,69 @@
+mod common;
+
+use std::f64::consts::PI;
+
+use conlaw::{bc, cl, methods, Domain, Problem, Resolution, Simulation};
+
+use common::{advection_error, advection_orders, component, l1_error, orders, run};
+
+#[test]
+fn second_order() {
+    let orders = advection_orders(methods::LaxWendroff::<f64>::default, 25, 0.5);
+    assert!(orders.iter().all(|&p| p > 1.9), "orders {orders:?}");
+}
+
+#[test]
+fn exact_at_unit_courant_number() {
+    // Lax-Wendroff reduces to the exact shift by one cell per step
+    let error = advection_error(methods::LaxWendroff::<f64>::default(), 50, 1.);
+    assert!(error < 1e-12, "error {error}");
+}
+
+#[test]
+fn more_accurate_than_lax_friedrichs() {
+    let lw = advection_error(methods::LaxWendroff::<f64>::default(), 100, 0.5);
+    let lf = advection_error(methods::LaxFriedrichs::<f64>::default(), 100, 0.5);
+    assert!(lw < 0.1 * lf, "Lax-Wendroff {lw}, Lax-Friedrichs {lf}");
+}
+
+/// L1 error on the density of a smooth density wave of the Euler equations, carried at unit speed
+/// once around the periodic domain `[0, 1]`
+fn density_wave_error(space_steps: usize) -> f64 {
+    let density = |x: f64| 1. + 0.2 * (2. * PI * x).sin();
+    let problem = Problem::new(
+        "density_wave",
+        cl::Euler::default(),
+        Domain {
+            time: (0., 1.),
+            space: (0., 1.),
+        },
+        bc::Periodic,
+        move |x, mut v| {
+            // unit velocity and pressure
+            v[(0, 0)] = density(x);
+            v[(1, 0)] = density(x);
+            v[(2, 0)] = 1. / 0.4 + 0.5 * density(x);
+        },
+    );
+    // largest wave speed 1 + c, with c < 1.4 for a density above 0.8
+    let sim = Simulation::new(problem)
+        .with_method::<methods::LaxWendroff<_>>()
+        .with_time_resolution(Resolution::Steps(3 * space_steps))
+        .with_space_resolution(Resolution::Steps(space_steps));
+
+    let u = component(&run(sim), 3, 0);
+    let exact: Vec<f64> = (0..=space_steps)
+        .map(|i| density(i as f64 / space_steps as f64))
+        .collect();
+    l1_error(&u, &exact)
+}
+
+#[test]
+fn nonlinear_system() {
+    let errors: Vec<f64> = [25, 50, 100, 200]
+        .into_iter()
+        .map(density_wave_error)
+        .collect();
+    let orders = orders(&errors);
+    assert!(orders.iter().all(|&p| p > 1.8), "orders {orders:?}");
+}
@@ -0,0 +1,215 @@
+mod common;
+
+use faer_core::Mat;
+
+use conlaw::{
+    bc,
+    cl::models::{BuckleyLeverett, Burgers, FundamentalDiagram, Isothermal, Lwr, PSystem},
+    fluxes, methods,
+    riemann::ExactRiemannSolution,
+    ConservationLaw, Domain, Problem, Resolution, Simulation,
+};
+
+use common::{component, l1_error, run};
+
+/// Exact solution of the Riemann problem between `left` and `right` of `law`, at `ξ`
+fn sample(law: &dyn ExactRiemannSolution<f64>, left: &[f64], right: &[f64], xi: f64) -> Vec<f64> {
+    let m = left.len();
+    let (left, right) = (
+        Mat::from_fn(m, 1, |i, _| left[i]),
+        Mat::from_fn(m, 1, |i, _| right[i]),
+    );
+    let mut u = Mat::<f64>::zeros(m, 1);
+    law.riemann_sample(left.as_ref(), right.as_ref(), xi, u.as_mut());
+    (0..m).map(|i| u.read(i, 0)).collect()
+}
+
+#[test]
+fn burgers() {
+    // a shock at speed 1/2, and a rarefaction u = ξ
+    assert_eq!(sample(&Burgers, &[1.], &[0.], 0.49), [1.]);
+    assert_eq!(sample(&Burgers, &[1.], &[0.], 0.51), [0.]);
+    for xi in [-0.5, 0.25, 0.75, 1.5] {
+        assert_eq!(sample(&Burgers, &[0.], &[1.], xi), [xi.clamp(0., 1.)]);
+    }
+}
+
+#[test]
+fn lwr() {
+    let lwr = Lwr::<f64>::default();
+    // denser traffic downstream: a shock at speed q(ρ_L) - q(ρ_R) / (ρ_L - ρ_R) = 1 - ρ_L - ρ_R
+    assert_eq!(sample(&lwr, &[0.2], &[0.6], 0.19), [0.2]);
+    assert_eq!(sample(&lwr, &[0.2], &[0.6], 0.21), [0.6]);
+    // a traffic light turning green: a rarefaction between the speeds -1 and 1
+    for xi in [-0.5, 0., 0.5] {
+        let rho = sample(&lwr, &[1.], &[0.], xi)[0];
+        assert!((lwr.diagram().speed(rho) - xi).abs() < 1e-15);
+    }
+}
+
+#[test]
+fn buckley_leverett_tangency() {
+    // water injected into oil: a rarefaction from 1, then a shock from the state u* where the
+    // chord to 0 is tangent to f, u* = √(M/(M + 1)) for this fractional flow
+    let bl = BuckleyLeverett::<f64>::default();
+    let star = (0.5f64 / 1.5).sqrt();
+    let speed = bl.flux(star) / star;
+    assert!((bl.speed(star) - speed).abs() < 1e-12);
+
+    let before = sample(&bl, &[1.], &[0.], speed - 1e-9)[0];
+    assert!(
+        (before - star).abs() < 1e-7,
+        "u* = {before} instead of {star}"
+    );
+    assert_eq!(sample(&bl, &[1.], &[0.], speed + 1e-9), [0.]);
+    for xi in [0.5, 1., 1.3] {
+        let u = sample(&bl, &[1.], &[0.], xi)[0];
+        assert!(
+            u > star && (bl.speed(u) - xi).abs() < 1e-10,
+            "u = {u} at {xi}"
+        );
+    }
+    assert_eq!(sample(&bl, &[1.], &[0.], -0.1), [1.]);
+}
+
+#[test]
+fn buckley_leverett_envelopes() {
+    let bl = BuckleyLeverett::<f64>::default();
+    let c = bl.inflection();
+    // the characteristic speed peaks at the inflection point
+    assert!(bl.speed(c) > bl.speed(c - 1e-4) && bl.speed(c) > bl.speed(c + 1e-4));
+
+    // from 0 to 1: a rarefaction over the convex part, then a shock tangent to f
+    let mut previous = 0.;
+    for k in 0..=200 {
+        let xi = 3. * k as f64 / 200.;
+        let u = sample(&bl, &[0.], &[1.], xi)[0];
+        assert!(u >= previous, "not monotone at {xi}");
+        previous = u;
+        if u > 0. && u < 1. {
+            assert!(u <= c && (bl.speed(u) - xi).abs() < 1e-9, "u = {u} at {xi}");
+        }
+    }
+    let tangency = |u: f64| bl.speed(u) - (bl.flux(1.) - bl.flux(u)) / (1. - u);
+    let (mut a, mut b) = (0., c);
+    for _ in 0..60 {
+        let m = 0.5 * (a + b);
+        if tangency(m) < 0. {
+            a = m;
+        } else {
+            b = m;
+        }
+    }
+    let (tail, speed) = (a, bl.speed(a));
+    let before = sample(&bl, &[0.], &[1.], speed - 1e-9)[0];
+    assert!(
+        (before - tail).abs() < 1e-7,
+        "tail {before} instead of {tail}"
+    );
+    assert_eq!(sample(&bl, &[0.], &[1.], speed + 1e-9), [1.]);
+
+    // states on both sides of the inflection point, close to each other: a single shock
+    let (ul, ur) = (c + 1e-4, c - 1e-4);
+    let speed = (bl.flux(ul) - bl.flux(ur)) / (ul - ur);
+    assert_eq!(sample(&bl, &[ul], &[ur], speed - 1e-6), [ul]);
+    assert_eq!(sample(&bl, &[ul], &[ur], speed + 1e-6), [ur]);
+}
+
+#[test]
+fn buckley_leverett_convergence() {
+    let bl = BuckleyLeverett::<f64>::default();
+    let problem = Problem::new(
+        "injection",
+        bl,
+        Domain {
+            time: (0., 0.4),
+            space: (0., 1.),
+        },
+        bc::Outflow::constant(),
+        |x, mut v| v[(0, 0)] = if x < 0.2 { 1. } else { 0. },
+    );
+    let sim = Simulation::new(problem)
+        .with_method_instance(methods::FiniteVolume::new(fluxes::Rusanov))
+        .with_time_resolution(Resolution::Steps(400))
+        .with_space_resolution(Resolution::Steps(200));
+    let u = run(sim);
+
+    let exact: Vec<f64> = (0..=200)
+        .map(|i| sample(&bl, &[1.], &[0.], (i as f64 / 200. - 0.2) / 0.4)[0])
+        .collect();
+    let error = l1_error(&u, &exact);
+    assert!(error < 3e-2, "error {error}");
+}
+
+#[test]
+fn isothermal() {
+    let law = Isothermal::<f64>::default();
+    // a rarefaction to the left and a shock to the right
+    let (left, right) = ([2., 0.], [1., 0.]);
+    let problem = Problem::new(
+        "isothermal",
+        law,
+        Domain {
+            time: (0., 0.2),
+            space: (0., 1.),
+        },
+        bc::Outflow::constant(),
+        move |x, mut v| {
+            let state = if x < 0.5 { left } else { right };
+            v[(0, 0)] = state[0];
+            v[(1, 0)] = state[1];
+        },
+    );
+    let sim = Simulation::new(problem)
+        .with_method_instance(methods::FiniteVolume::new(fluxes::Hll))
+        .with_time_resolution(Resolution::Steps(200))
+        .with_space_resolution(Resolution::Steps(200));
+    let density = component(&run(sim), 2, 0);
+
+    let exact: Vec<f64> = (0..=200)
+        .map(|i| sample(&law, &left, &right, (i as f64 / 200. - 0.5) / 0.2)[0])
+        .collect();
+    let error = l1_error(&density, &exact);
+    assert!(error < 2e-2, "error {error}");
+
+    // Rankine-Hugoniot conditions across the right shock
+    let star = sample(&law, &left, &right, 0.);
+    let (rho, m) = (star[0], star[1]);
+    let speed = m / (rho - 1.);
+    let momentum_flux = m * m / rho + rho;
+    assert!((momentum_flux - 1. - speed * m).abs() < 1e-10);
+
+    // expanding into a vacuum on either side: the density decays as ρ exp(-(ξ + c)/c) without
+    // ever vanishing
+    for xi in [-2., -1., 0., 1., 3.] {
+        let expected = if xi < -1. { 1. } else { (-xi - 1f64).exp() };
+        let u = sample(&law, &[1., 0.], &[0., 0.], xi);
+        assert!((u[0] - expected).abs() < 1e-12, "density {} at {xi}", u[0]);
+        let u = sample(&law, &[0., 0.], &[1., 0.], -xi);
+        assert!(
+            (u[0] - expected).abs() < 1e-12,
+            "density {} at {}",
+            u[0],
+            -xi
+        );
+    }
+    assert_eq!(sample(&law, &[0., 0.], &[0., 0.], 0.), [0., 0.]);
+}
+
+#[test]
+fn p_system() {
+    // isothermal pressure law p = 1/v, of Lagrangian sound speed 1/v
+    let law = PSystem::new(|v: f64| 1. / v);
+    for v in [0.5, 1., 2.] {
+        assert!((law.sound_speed(v) - 1. / v).abs() < 1e-8);
+        let u = Mat::from_fn(2, 1, |i, _| [v, 0.3][i]);
+        let eigen = law.eigen_decomposition(u.as_ref()).unwrap();
+        assert!((eigen.values.read(0, 0) + 1. / v).abs() < 1e-8);
+        assert!((eigen.values.read(1, 0) - 1. / v).abs() < 1e-8);
+        let identity = &eigen.left * &eigen.right;
+        for (i, j) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            let expected = if i == j { 1. } else { 0. };
+            assert!((identity.read(i, j) - expected).abs() < 1e-12);
+        }
+    }
+}
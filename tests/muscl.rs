@@ -0,0 +1,159 @@
+mod common;
+
+use conlaw::{
+    bc, cl, fluxes,
+    methods::{self, Limiter},
+    Domain, Problem, Resolution, Simulation,
+};
+
+use common::{advection_error, advection_orders, run};
+
+const LIMITERS: [Limiter; 5] = [
+    Limiter::Minmod,
+    Limiter::Superbee,
+    Limiter::VanLeer,
+    Limiter::MC,
+    Limiter::VanAlbada,
+];
+
+#[test]
+fn limiter_values() {
+    let cases = [
+        (Limiter::Minmod, 1., 3., 1.),
+        (Limiter::Superbee, 1., 3., 2.),
+        (Limiter::Superbee, 1., 1.5, 1.5),
+        (Limiter::VanLeer, 1., 3., 1.5),
+        (Limiter::MC, 1., 3., 2.),
+        (Limiter::MC, 1., 1.5, 1.25),
+        (Limiter::VanAlbada, 1., 3., 1.2),
+    ];
+    for (limiter, a, b, exact) in cases {
+        let slope: f64 = limiter.slope(a, b);
+        assert!(
+            (slope - exact).abs() < 1e-15,
+            "{limiter:?} slope {slope} instead of {exact} between {a} and {b}"
+        );
+    }
+}
+
+#[test]
+fn limiter_properties() {
+    for limiter in LIMITERS {
+        for (a, b) in [(1., 3.), (0.5, 0.2), (2., 2.)] {
+            let slope: f64 = limiter.slope(a, b);
+            // symmetric, odd, within the TVD region, and exact on linear data
+            assert_eq!(slope, limiter.slope(b, a), "{limiter:?}");
+            assert_eq!(-slope, limiter.slope(-a, -b), "{limiter:?}");
+            assert!(slope >= f64::min(a, b) - 1e-15, "{limiter:?}");
+            assert!(slope <= 2. * f64::min(a, b) + 1e-15, "{limiter:?}");
+            assert!(slope <= f64::max(a, b) + 1e-15, "{limiter:?}");
+        }
+        assert_eq!(limiter.slope(2., 2.), 2., "{limiter:?}");
+        // zero at extrema
+        for (a, b) in [(1., -1.), (-2., 0.5), (0., 1.), (1., 0.)] {
+            assert_eq!(limiter.slope(a, b), 0., "{limiter:?}");
+        }
+    }
+}
+
+fn muscl(limiter: Limiter) -> methods::Muscl<f64> {
+    methods::MusclHancock::new(fluxes::Rusanov, limiter)
+}
+
+#[test]
+fn second_order() {
+    // the limiters clip the extrema of the sine, where the order drops locally
+    for limiter in [Limiter::VanLeer, Limiter::MC] {
+        let orders = advection_orders(|| muscl(limiter), 25, 0.4);
+        assert!(
+            orders.iter().all(|&p| p > 1.7),
+            "{limiter:?}: orders {orders:?}"
+        );
+    }
+    let orders = advection_orders(|| muscl(Limiter::Minmod), 25, 0.4);
+    assert!(orders.iter().all(|&p| p > 1.4), "Minmod: orders {orders:?}");
+}
+
+#[test]
+fn more_accurate_than_first_order() {
+    let muscl = advection_error(muscl(Limiter::VanLeer), 100, 0.4);
+    let rusanov = advection_error(methods::FiniteVolume::new(fluxes::Rusanov), 100, 0.4);
+    assert!(muscl < 0.1 * rusanov, "MUSCL {muscl}, Rusanov {rusanov}");
+}
+
+/// Total variation of `u`
+fn total_variation(u: &[f64]) -> f64 {
+    u.windows(2).map(|w| (w[1] - w[0]).abs()).sum()
+}
+
+#[test]
+fn no_new_extrema() {
+    // a square wave advected once around the periodic domain: no overshoot nor undershoot, and
+    // the total variation does not grow
+    for limiter in LIMITERS {
+        let problem = Problem::new(
+            "square_wave",
+            cl::Scalar::new(|u: f64| u),
+            Domain {
+                time: (0., 1.),
+                space: (0., 1.),
+            },
+            bc::Periodic,
+            |x, mut v| v[(0, 0)] = if (0.25..0.5).contains(&x) { 1. } else { 0. },
+        );
+        let sim = Simulation::new(problem)
+            .with_method_instance(muscl(limiter))
+            .with_time_resolution(Resolution::Steps(250))
+            .with_space_resolution(Resolution::Steps(100));
+        let u = run(sim);
+        assert!(
+            u.iter().all(|&u| (-1e-12..=1. + 1e-12).contains(&u)),
+            "{limiter:?}: values in [{}, {}]",
+            u.iter().copied().fold(f64::INFINITY, f64::min),
+            u.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+        );
+        let tv = total_variation(&u);
+        assert!(tv <= 2. + 1e-12, "{limiter:?}: total variation {tv}");
+    }
+}
+
+#[test]
+fn characteristic_variables() {
+    // a system of two decoupled advection equations reconstructed in characteristic variables
+    // matches the component-wise reconstruction of each
+    let problem = |speeds: [f64; 2]| {
+        Problem::new(
+            "decoupled",
+            cl::General::new(
+                2,
+                move |u: faer_core::MatRef<f64>, mut v: faer_core::MatMut<f64>| {
+                    v[(0, 0)] = speeds[0] * u[(0, 0)];
+                    v[(1, 0)] = speeds[1] * u[(1, 0)];
+                },
+            ),
+            Domain {
+                time: (0., 0.5),
+                space: (0., 1.),
+            },
+            bc::Periodic,
+            |x, mut v| {
+                v[(0, 0)] = if x < 0.5 { 1. } else { 0. };
+                v[(1, 0)] = (2. * std::f64::consts::PI * x).sin();
+            },
+        )
+    };
+    let solve = |variables| {
+        let sim = Simulation::new(problem([1., -0.5]))
+            .with_method_instance(muscl(Limiter::VanLeer).with_variables(variables))
+            .with_time_resolution(Resolution::Steps(125))
+            .with_space_resolution(Resolution::Steps(100));
+        run(sim)
+    };
+    let (conserved, characteristic) = (
+        solve(methods::Variables::Conserved),
+        solve(methods::Variables::characteristic()),
+    );
+    for (u, v) in conserved.iter().zip(&characteristic) {
+        assert!((u - v).abs() < 1e-8, "{u} and {v}");
+    }
+}
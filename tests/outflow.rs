@@ -0,0 +1,87 @@
+mod common;
+
+use faer_core::{MatMut, MatRef};
+
+use conlaw::{bc, cl, methods, Domain, Problem, Resolution, Simulation};
+
+use common::{assert_cell, assert_close, ghost_cells, run};
+
+/// Problem on `[0, 1]` with initial data `u0`, to inspect the ghost cells of `bc`
+fn problem(bc: bc::Outflow, u0: fn(f64, MatMut<f64>)) -> Problem<'static, f64> {
+    Problem::new(
+        "outflow",
+        cl::General::new(2, |u: MatRef<f64>, mut v: MatMut<f64>| v.clone_from(u)),
+        Domain {
+            time: (0., 1.),
+            space: (0., 1.),
+        },
+        bc,
+        u0,
+    )
+}
+
+fn affine_and_quadratic(x: f64, mut v: MatMut<f64>) {
+    v[(0, 0)] = 1. + 2. * x;
+    v[(1, 0)] = x * x;
+}
+
+#[test]
+fn constant() {
+    let ghosts = &ghost_cells(
+        problem(bc::Outflow::constant(), affine_and_quadratic),
+        1,
+        10,
+    )[0];
+    for k in 0..2 {
+        assert_cell(&ghosts.left[k], &[1., 0.]);
+        assert_cell(&ghosts.right[k], &[3., 1.]);
+    }
+}
+
+#[test]
+fn linear() {
+    let ghosts = &ghost_cells(problem(bc::Outflow::linear(), affine_and_quadratic), 1, 10)[0];
+    for k in 0..2 {
+        let d = (k + 1) as f64;
+        // exact for the linear component, from the two outermost cells for the quadratic one
+        assert_cell(&ghosts.left[k], &[1. - 0.2 * d, -0.01 * d]);
+        assert_cell(&ghosts.right[k], &[3. + 0.2 * d, 1. + 0.19 * d]);
+    }
+}
+
+#[test]
+fn single_cell() {
+    // no slope to continue, the boundary cell is copied
+    let u0 = |_, mut v: MatMut<f64>| {
+        v[(0, 0)] = 1.;
+        v[(1, 0)] = 0.;
+    };
+    let ghosts = &ghost_cells(problem(bc::Outflow::linear(), u0), 1, 0)[0];
+    for k in 0..2 {
+        assert_cell(&ghosts.left[k], &[1., 0.]);
+        assert_cell(&ghosts.right[k], &[1., 0.]);
+    }
+}
+
+#[test]
+fn pulse_leaves_domain() {
+    // a pulse advected through the right boundary without reflecting back
+    let problem = Problem::new(
+        "outflow_pulse",
+        cl::Scalar::new(|u: f64| u),
+        Domain {
+            time: (0., 1.),
+            space: (0., 1.),
+        },
+        bc::Outflow::constant(),
+        |x, mut v| v[(0, 0)] = (-((x - 0.5) / 0.1).powi(2)).exp(),
+    );
+
+    let sim = Simulation::new(problem)
+        .with_method::<methods::LaxWendroff<_>>()
+        .with_time_resolution(Resolution::Steps(200))
+        .with_space_resolution(Resolution::Steps(100));
+
+    let u = run(sim);
+    assert_close(&u, 0..101, 0., 0.01);
+}
@@ -0,0 +1,81 @@
+use std::f64::consts::PI;
+
+use faer_core::{MatMut, MatRef};
+
+use conlaw::{
+    bc, cl, methods, Domain, Driver, Method, ObsCtx, Observer, Problem, Resolution, SimError,
+    Simulation,
+};
+
+/// Keeps a copy of the final solution
+struct Final<'a>(&'a mut Vec<f64>);
+
+impl Observer<f64> for Final<'_> {
+    fn at_cleanup(&mut self, ctx: ObsCtx<f64>) -> Result<(), SimError> {
+        let u = ctx.solution();
+        self.0.extend((0..u.nrows()).map(|i| u.read(i, 0)));
+        Ok(())
+    }
+}
+
+/// Advects `sin(2πx)` and `cos(2πx)` at speed `a` on the periodic domain `[0, 1]` until
+/// `t = 0.25`, with a Courant number of 1 for which upwinding shifts the solution by exactly one
+/// cell per step
+fn shift<M: Method<f64> + Default>(a: f64) -> Vec<f64> {
+    let problem = Problem::new(
+        "periodic_shift",
+        cl::General::new(2, move |u: MatRef<f64>, mut v: MatMut<f64>| {
+            v[(0, 0)] = a * u[(0, 0)];
+            v[(1, 0)] = a * u[(1, 0)];
+        }),
+        Domain {
+            time: (0., 0.25),
+            space: (0., 1.),
+        },
+        bc::Periodic,
+        |x, mut v| {
+            v[(0, 0)] = (2. * PI * x).sin();
+            v[(1, 0)] = (2. * PI * x).cos();
+        },
+    );
+
+    let sim = Simulation::new(problem)
+        .with_method::<M>()
+        .with_time_resolution(Resolution::Steps(25))
+        .with_space_resolution(Resolution::Steps(100));
+
+    let mut solution = Vec::new();
+    Driver::new(sim)
+        .with_observer(Final(&mut solution))
+        .run()
+        .expect("failed to run simulation");
+    solution
+}
+
+fn assert_shifted(u: &[f64], a: f64) {
+    assert_eq!(u.len(), 2 * 101);
+    for (i, cell) in u.chunks(2).enumerate() {
+        let x = i as f64 / 100. - a * 0.25;
+        let exact = [(2. * PI * x).sin(), (2. * PI * x).cos()];
+        for k in 0..2 {
+            assert!(
+                (cell[k] - exact[k]).abs() < 1e-9,
+                "u[{i}][{k}] = {} instead of {}",
+                cell[k],
+                exact[k]
+            );
+        }
+    }
+}
+
+#[test]
+fn left_ghost_cells() {
+    let u = shift::<methods::UpwindLeft<_>>(1.);
+    assert_shifted(&u, 1.);
+}
+
+#[test]
+fn right_ghost_cells() {
+    let u = shift::<methods::UpwindRight<_>>(-1.);
+    assert_shifted(&u, -1.);
+}
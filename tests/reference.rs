@@ -0,0 +1,128 @@
+use std::f64::consts::PI;
+
+use faer_core::{MatMut, MatRef};
+
+use conlaw::{cl, reference::EntropySolution, Grid};
+
+fn burgers() -> cl::Scalar<f64, impl Fn(f64) -> f64> {
+    cl::Scalar::new(|u: f64| 0.5 * u * u)
+}
+
+fn assert_near(value: f64, exact: f64, tolerance: f64, at: f64) {
+    assert!(
+        (value - exact).abs() < tolerance,
+        "{value} instead of {exact} at {at}"
+    );
+}
+
+#[test]
+fn shock() {
+    // Burgers' shock from 2 to 1 at the speed 3/2
+    let solution = EntropySolution::piecewise_constant(burgers(), &[0.], &[2., 1.]);
+    for x in [-1., 0., 0.5, 1.49, 1.51, 2.] {
+        let exact = if x < 1.5 { 2. } else { 1. };
+        assert_eq!(solution.eval(x, 1.), exact, "at {x}");
+    }
+
+    // turned into a rarefaction by a concave flux, its fan being u = -ξ
+    let concave = cl::Scalar::new(|u: f64| -0.5 * u * u);
+    let solution = EntropySolution::piecewise_constant(concave, &[0.], &[2., 1.]);
+    for x in [-3., -1.5, -1.2, 0.] {
+        assert_near(solution.eval(x, 1.), (-x).clamp(1., 2.), 1e-7, x);
+    }
+
+    // two shocks merging at t = 1/2, x = 3/4, into a shock at the speed 1
+    let solution = EntropySolution::piecewise_constant(burgers(), &[0., 0.5], &[2., 1., 0.]);
+    for (x, exact) in [(0.35, 2.), (0.4, 1.), (0.6, 1.), (0.65, 0.)] {
+        assert_eq!(solution.eval(x, 0.25), exact, "at {x}");
+    }
+    for (x, exact) in [(1.2, 2.), (1.3, 0.)] {
+        assert_eq!(solution.eval(x, 1.), exact, "at {x}");
+    }
+}
+
+#[test]
+fn rarefaction() {
+    // transonic fan u = x/t between -1 and 1
+    let solution = EntropySolution::piecewise_constant(burgers(), &[0.], &[-1., 1.]);
+    for x in [-2., -1., -0.5, 0., 0.25, 0.99, 1.5] {
+        assert_near(solution.eval(x, 1.), x.clamp(-1., 1.), 1e-7, x);
+    }
+    // same from smooth data, a steep ramp from -1 to 1 across [-0.001, 0.001]
+    let solution =
+        EntropySolution::smooth(burgers(), |x: f64| (1000. * x).clamp(-1., 1.), (-1., 1.));
+    for x in [-2., -0.5, 0.25, 0.8] {
+        assert_near(solution.eval(x, 2.), (x / 2.).clamp(-1., 1.), 1e-3, x);
+    }
+}
+
+#[test]
+fn sine_past_breaking() {
+    // u0 = sin 2πx breaks at t = 1/2π into a shock standing at x = 1/2 by symmetry
+    let solution = EntropySolution::periodic(burgers(), |x: f64| (2. * PI * x).sin(), (0., 1.));
+    let t = 0.3;
+    let u = solution.eval_on(Grid::from_steps(0., 1., 100), t);
+
+    // the smooth parts follow the characteristics
+    for (i, &u) in u.iter().enumerate() {
+        let x = i as f64 / 100.;
+        if (x - 0.5).abs() > 0.02 {
+            assert_near(u, (2. * PI * (x - t * u)).sin(), 1e-8, x);
+        }
+    }
+    // odd about the shock, its amplitude being the root of u = sin(2π t u)
+    for k in 1..10 {
+        assert_near(u[50 - k], -u[50 + k], 1e-8, k as f64);
+    }
+    let (mut lo, mut hi) = (0.5, 1.);
+    for _ in 0..60 {
+        let m = 0.5 * (lo + hi);
+        if m < (2. * PI * t * m).sin() {
+            lo = m;
+        } else {
+            hi = m;
+        }
+    }
+    let amplitude = solution.eval(0.5 - 1e-9, t);
+    assert_near(amplitude, lo, 1e-6, 0.5);
+    assert_near(solution.eval(0.5 + 1e-9, t), -lo, 1e-6, 0.5);
+
+    // periodicity
+    assert_near(solution.eval(1.3, t), u[30], 1e-12, 1.3);
+    assert_near(solution.eval(-0.7, t), u[30], 1e-12, -0.7);
+
+    // before breaking, continuous and along the characteristics everywhere
+    for x in [0.1, 0.45, 0.5, 0.55, 0.9] {
+        let u = solution.eval(x, 0.1);
+        assert_near(u, (2. * PI * (x - 0.1 * u)).sin(), 1e-8, x);
+    }
+}
+
+#[test]
+fn close_roots() {
+    // a narrow pulse of height 2 on [0, w] spreads into the triangle u = x/t on [0, √(4wt)]:
+    // the characteristic equation changes sign twice within w/t, a small fraction of the range of
+    // the data, and its roots are only found by a scan resolving the data
+    let w = 1. / 1024.;
+    let pulse = move |x: f64| if (0. ..w).contains(&x) { 2. } else { 0. };
+    let solution = EntropySolution::smooth(burgers(), pulse, (-1., 1.));
+    let end = (4. * w).sqrt();
+    for x in [-0.01, 0.01, 0.03, 0.05, 0.06, end + 0.001, 0.1] {
+        let exact = if (0. ..end).contains(&x) { x } else { 0. };
+        assert_near(solution.eval(x, 1.), exact, 1e-3, x);
+    }
+}
+
+#[test]
+#[should_panic(expected = "scalar conservation law")]
+fn system() {
+    let law = cl::General::new(2, |u: MatRef<f64>, mut v: MatMut<f64>| v.clone_from(u));
+    EntropySolution::piecewise_constant(law, &[0.], &[1., 0.]);
+}
+
+#[test]
+#[should_panic(expected = "neither strictly convex nor strictly concave")]
+fn not_convex() {
+    let cubic = cl::Scalar::new(|u: f64| u * u * u);
+    EntropySolution::piecewise_constant(cubic, &[0.], &[-1., 1.]);
+}
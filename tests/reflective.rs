@@ -0,0 +1,89 @@
+mod common;
+
+use faer_core::{MatMut, MatRef};
+
+use conlaw::{bc, cl, fluxes, methods, Domain, Problem, Resolution, Simulation};
+
+use common::{assert_cell, component, ghost_cells, run};
+
+/// Problem on `[0, 1]` with initial data `(1 + x, 2x, x²)`, to inspect the ghost cells of `bc`
+fn problem(bc: bc::Reflective) -> Problem<'static, f64> {
+    Problem::new(
+        "reflective",
+        cl::General::new(3, |u: MatRef<f64>, mut v: MatMut<f64>| v.clone_from(u)),
+        Domain {
+            time: (0., 1.),
+            space: (0., 1.),
+        },
+        bc,
+        |x, mut v| {
+            v[(0, 0)] = 1. + x;
+            v[(1, 0)] = 2. * x;
+            v[(2, 0)] = x * x;
+        },
+    )
+}
+
+#[test]
+fn mirror() {
+    let ghosts = &ghost_cells(problem(bc::Reflective::new(3, [1])), 1, 10)[0];
+    for k in 0..2 {
+        // interior cell at the same distance from the wall
+        let d = 0.1 * (k + 1) as f64;
+        assert_cell(&ghosts.left[k], &[1. + d, -2. * d, d * d]);
+        let x = 1. - d;
+        assert_cell(&ghosts.right[k], &[1. + x, -2. * x, x * x]);
+    }
+}
+
+#[test]
+#[should_panic(expected = "component 3 changing sign at the wall is not in a system of size 3")]
+fn odd_component_out_of_system() {
+    bc::Reflective::new(3, [1, 3]);
+}
+
+#[test]
+#[should_panic(expected = "reflective wall needs more than 2 cells to mirror, the mesh has 2")]
+fn too_few_cells() {
+    ghost_cells(problem(bc::Reflective::new(3, [1])), 1, 1);
+}
+
+#[test]
+fn closed_basin() {
+    // a hump of water sloshing between two walls, no mass leaving the basin
+    let depth = |x: f64| 1. + 0.5 * (-((x - 0.3) / 0.1).powi(2)).exp();
+    let problem = Problem::new(
+        "closed_basin",
+        cl::ShallowWater::default(),
+        Domain {
+            time: (0., 1.),
+            space: (0., 1.),
+        },
+        bc::Reflective::new(2, [1]),
+        move |x, mut v| {
+            v[(0, 0)] = depth(x);
+            v[(1, 0)] = 0.;
+        },
+    );
+
+    let u = run(Simulation::new(problem)
+        .with_method::<methods::FiniteVolume<_, fluxes::Rusanov>>()
+        .with_time_resolution(Resolution::Steps(500))
+        .with_space_resolution(Resolution::Steps(100)));
+    let (h, hu) = (component(&u, 2, 0), component(&u, 2, 1));
+
+    // the walls are on the boundary cells, which only hold half of their volume in the basin
+    let trapezoid = |h: &[f64]| h.iter().sum::<f64>() - (h[0] + h[h.len() - 1]) / 2.;
+    let initial = trapezoid(
+        &(0..=100)
+            .map(|i| depth(i as f64 / 100.))
+            .collect::<Vec<_>>(),
+    );
+    let mass = trapezoid(&h);
+    assert!(
+        (mass - initial).abs() < 1e-10,
+        "mass {mass} instead of {initial}"
+    );
+    // no flow through the walls
+    assert!(hu[0].abs() < 1e-12 && hu[100].abs() < 1e-12, "{hu:?}");
+}
@@ -0,0 +1,229 @@
+mod common;
+
+use faer_core::{Mat, MatMut, MatRef};
+
+use conlaw::{
+    bc, cl,
+    fluxes::{self, NumericalFlux, RoeAverage},
+    methods, ConservationLaw, Domain, Problem, Resolution, Simulation,
+};
+
+use common::{component, l1_error, run};
+
+fn state(values: &[f64]) -> Mat<f64> {
+    Mat::from_fn(values.len(), 1, |i, _| values[i])
+}
+
+fn flux_of(cl: &dyn ConservationLaw<f64>, u: &Mat<f64>) -> Mat<f64> {
+    let mut f = Mat::<f64>::zeros(u.nrows(), 1);
+    cl.flux_function(u.as_ref(), f.as_mut());
+    f
+}
+
+/// Asserts that the Jacobian at the average of `left` and `right` maps their difference to the
+/// difference of their fluxes
+fn assert_roe_property(
+    cl: &dyn ConservationLaw<f64>,
+    average: &dyn RoeAverage<f64>,
+    left: &[f64],
+    right: &[f64],
+) {
+    let (left, right) = (state(left), state(right));
+    let m = left.nrows();
+    let mut u = Mat::<f64>::zeros(m, 1);
+    average.average(cl, left.as_ref(), right.as_ref(), u.as_mut());
+    let mut jac = Mat::<f64>::zeros(m, m);
+    cl.jacobian(u.as_ref(), jac.as_mut());
+
+    let (fl, fr) = (flux_of(cl, &left), flux_of(cl, &right));
+    for i in 0..m {
+        let jump: f64 = (0..m)
+            .map(|j| jac.read(i, j) * (right.read(j, 0) - left.read(j, 0)))
+            .sum();
+        let exact = fr.read(i, 0) - fl.read(i, 0);
+        assert!(
+            (jump - exact).abs() < 1e-10 * (1. + exact.abs()),
+            "component {i}: {jump} instead of {exact}"
+        );
+    }
+}
+
+fn roe_flux(
+    roe: &dyn NumericalFlux<f64>,
+    cl: &dyn ConservationLaw<f64>,
+    left: &[f64],
+    right: &[f64],
+) -> Vec<f64> {
+    let (left, right) = (state(left), state(right));
+    let mut flux = Mat::<f64>::zeros(left.nrows(), 1);
+    roe.flux(cl, left.as_ref(), right.as_ref(), flux.as_mut());
+    (0..flux.nrows()).map(|i| flux.read(i, 0)).collect()
+}
+
+#[test]
+fn euler_roe_average() {
+    // Sod's states, and a strong shock
+    let sod = ([1., 0., 2.5], [0.125, 0., 0.25]);
+    let shock = (
+        [
+            5.99924,
+            5.99924 * 19.5975,
+            460.894 / 0.4 + 0.5 * 5.99924 * 19.5975 * 19.5975,
+        ],
+        [
+            5.99242,
+            -5.99242 * 6.19633,
+            46.095 / 0.4 + 0.5 * 5.99242 * 6.19633 * 6.19633,
+        ],
+    );
+    let ideal = cl::Euler::<f64>::default();
+    for (left, right) in [sod, shock] {
+        assert_roe_property(&ideal, &fluxes::EulerRoeAverage, &left, &right);
+    }
+
+    // a stiffened gas, the pressure being recovered from the law's own equation of state
+    let water = cl::Euler::from_eos(cl::StiffenedGas::new(4.4, 6.));
+    let mut left = Mat::<f64>::zeros(3, 1);
+    let mut right = Mat::<f64>::zeros(3, 1);
+    water.conserved(
+        &cl::Primitive {
+            density: 1.,
+            velocity: 0.5,
+            pressure: 2.,
+        },
+        left.as_mut(),
+    );
+    water.conserved(
+        &cl::Primitive {
+            density: 0.8,
+            velocity: -0.3,
+            pressure: 1.,
+        },
+        right.as_mut(),
+    );
+    let values = |u: &Mat<f64>| [u.read(0, 0), u.read(1, 0), u.read(2, 0)];
+    assert_roe_property(
+        &water,
+        &fluxes::EulerRoeAverage,
+        &values(&left),
+        &values(&right),
+    );
+}
+
+#[test]
+fn shallow_water_roe_average() {
+    for g in [1., 9.81] {
+        let sw = cl::ShallowWater::new(g);
+        assert_roe_property(
+            &sw,
+            &fluxes::ShallowWaterRoeAverage,
+            &[2., 1.],
+            &[0.5, -0.2],
+        );
+        assert_roe_property(&sw, &fluxes::ShallowWaterRoeAverage, &[1., 0.], &[3., 4.5]);
+    }
+}
+
+#[test]
+fn single_waves() {
+    // only one wave is excited between the Roe average of the states: the flux of a contact is
+    // upwind even though the acoustic waves run in both directions, and a stationary shock is
+    // kept in place
+    let euler = cl::Euler::<f64>::default();
+    let roe = fluxes::Roe::new(fluxes::EulerRoeAverage);
+    let contact = ([1., 0.5, 2.625], [0.125, 0.0625, 2.515625]);
+    // Mach 2 shock: density ratio 8/3, pressure ratio 9/2
+    let (u, p) = (2. * 1.4f64.sqrt(), 1.);
+    let (v, q) = (u * 3. / 8., p * 4.5);
+    let shock = (
+        [1., u, p / 0.4 + 0.5 * u * u],
+        [8. / 3., u, q / 0.4 + 0.5 * 8. / 3. * v * v],
+    );
+    for (left, right) in [contact, shock] {
+        let exact = flux_of(&euler, &state(&left));
+        let flux = roe_flux(&roe, &euler, &left, &right);
+        for (i, f) in flux.iter().enumerate() {
+            assert!((f - exact.read(i, 0)).abs() < 1e-12, "flux {flux:?}");
+        }
+    }
+
+    // not so with the arithmetic average
+    let (left, right) = shock;
+    let exact = flux_of(&euler, &state(&left));
+    let flux = roe_flux(
+        &fluxes::Roe::<fluxes::ArithmeticAverage>::default(),
+        &euler,
+        &left,
+        &right,
+    );
+    let error = (0..3)
+        .map(|i| (flux[i] - exact.read(i, 0)).abs())
+        .fold(0., f64::max);
+    assert!(error > 1e-3, "flux {flux:?}");
+}
+
+#[test]
+fn not_diagonalizable() {
+    // Jordan block: the HLL flux, upwind with both wave speeds equal to 1
+    let jordan = cl::General::new(2, |u: MatRef<f64>, mut v: MatMut<f64>| {
+        v[(0, 0)] = u[(0, 0)] + u[(1, 0)];
+        v[(1, 0)] = u[(1, 0)];
+    });
+    let flux = roe_flux(
+        &fluxes::Roe::<fluxes::ArithmeticAverage>::default(),
+        &jordan,
+        &[1., 2.],
+        &[3., 5.],
+    );
+    assert_eq!(flux, [3., 2.]);
+
+    // dry shallow water on both sides
+    let flux = roe_flux(
+        &fluxes::Roe::new(fluxes::ShallowWaterRoeAverage),
+        &cl::ShallowWater::default(),
+        &[0., 0.],
+        &[0., 0.],
+    );
+    assert_eq!(flux, [0., 0.]);
+}
+
+#[test]
+fn sod() {
+    let euler = cl::Euler::<f64>::default();
+    let (left, right) = (
+        cl::Primitive {
+            density: 1.,
+            velocity: 0.,
+            pressure: 1.,
+        },
+        cl::Primitive {
+            density: 0.125,
+            velocity: 0.,
+            pressure: 0.1,
+        },
+    );
+    let problem = Problem::new(
+        "sod",
+        euler,
+        Domain {
+            time: (0., 0.2),
+            space: (0., 1.),
+        },
+        bc::Outflow::constant(),
+        move |x, v| euler.conserved(if x < 0.5 { &left } else { &right }, v),
+    );
+    let sim = Simulation::new(problem)
+        .with_method_instance(methods::FiniteVolume::new(fluxes::Roe::new(
+            fluxes::EulerRoeAverage,
+        )))
+        .with_time_resolution(Resolution::Steps(200))
+        .with_space_resolution(Resolution::Steps(200));
+
+    let density = component(&run(sim), 3, 0);
+    let solution = euler.riemann(&left, &right);
+    let exact: Vec<f64> = (0..=200)
+        .map(|i| solution.sample((i as f64 / 200. - 0.5) / 0.2).density)
+        .collect();
+    let error = l1_error(&density, &exact);
+    assert!(error < 2e-2, "error {error}");
+}
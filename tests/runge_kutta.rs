@@ -0,0 +1,132 @@
+mod common;
+
+use std::{cell::RefCell, rc::Rc};
+
+use faer_core::MatMut;
+
+use conlaw::{
+    bc, cl,
+    methods::{Integrator, MethodOfLines},
+    ConservationLaw, Ctx, Domain, Problem, Resolution, Simulation, SpatialOperator,
+};
+
+use common::{orders, run};
+
+/// Right-hand side `L(u, t) = u cos t` in every cell, regardless of the flux, for which
+/// `u(t) = exp(sin t)` from `u(0) = 1`
+struct Ode;
+
+impl SpatialOperator<f64> for Ode {
+    fn left_ghost_cells(&self) -> usize {
+        1
+    }
+
+    fn right_ghost_cells(&self) -> usize {
+        1
+    }
+
+    fn init(&mut self, _ctx: Ctx<f64>) {}
+
+    fn rhs(&mut self, ctx: Ctx<f64>, _flux: &dyn ConservationLaw<f64>, mut rhs: MatMut<f64>) {
+        let u = ctx.slide(0);
+        for i in 0..rhs.nrows() {
+            rhs.write(i, 0, u.read(i, 0) * ctx.t.cos());
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "ODE"
+    }
+}
+
+/// Error of `integrator` at `t = 1` with `time_steps` steps
+fn error(integrator: Integrator, time_steps: usize) -> f64 {
+    let problem = Problem::new(
+        "ode",
+        cl::Scalar::new(|u: f64| u),
+        Domain {
+            time: (0., 1.),
+            space: (0., 1.),
+        },
+        bc::Periodic,
+        |_, mut v| v[(0, 0)] = 1.,
+    );
+    let sim = Simulation::new(problem)
+        .with_method_instance(MethodOfLines::new(Ode, integrator))
+        .with_time_resolution(Resolution::Steps(time_steps))
+        .with_space_resolution(Resolution::Steps(4));
+    let u = run(sim);
+    (u[0] - 1f64.sin().exp()).abs()
+}
+
+#[test]
+fn tableau_orders() {
+    for (integrator, order) in [
+        (Integrator::ForwardEuler, 1.),
+        (Integrator::SspRk2, 2.),
+        (Integrator::SspRk3, 3.),
+        (Integrator::Rk4, 4.),
+    ] {
+        let errors: Vec<f64> = [10, 20, 40, 80].map(|n| error(integrator, n)).into();
+        let orders = orders(&errors);
+        assert!(
+            orders.iter().all(|&p| (p - order).abs() < 0.2),
+            "{integrator:?}: orders {orders:?}"
+        );
+    }
+}
+
+/// Time of the Runge-Kutta stages of each step, one entry per call to the operator
+struct StageTimes(Rc<RefCell<Vec<f64>>>);
+
+impl SpatialOperator<f64> for StageTimes {
+    fn left_ghost_cells(&self) -> usize {
+        1
+    }
+
+    fn right_ghost_cells(&self) -> usize {
+        1
+    }
+
+    fn init(&mut self, _ctx: Ctx<f64>) {}
+
+    fn rhs(&mut self, ctx: Ctx<f64>, _flux: &dyn ConservationLaw<f64>, mut rhs: MatMut<f64>) {
+        self.0.borrow_mut().push(ctx.t);
+        rhs.fill_zeros();
+    }
+
+    fn name(&self) -> &'static str {
+        "Stage times"
+    }
+}
+
+#[test]
+fn stage_times() {
+    let times = Rc::new(RefCell::new(Vec::new()));
+    let problem = Problem::new(
+        "ode",
+        cl::Scalar::new(|u: f64| u),
+        Domain {
+            time: (0., 0.5),
+            space: (0., 1.),
+        },
+        bc::Periodic,
+        |_, mut v| v[(0, 0)] = 1.,
+    );
+    let sim = Simulation::new(problem)
+        .with_method_instance(MethodOfLines::new(
+            StageTimes(times.clone()),
+            Integrator::SspRk3,
+        ))
+        .with_time_resolution(Resolution::Steps(2))
+        .with_space_resolution(Resolution::Steps(4));
+    run(sim);
+
+    // t_n + c_i Δt with c = (0, 1, 1/2) and Δt = 1/4
+    let expected = [0., 0.25, 0.125, 0.25, 0.5, 0.375];
+    let times = times.borrow();
+    assert_eq!(times.len(), expected.len());
+    for (t, expected) in times.iter().zip(expected) {
+        assert!((t - expected).abs() < 1e-15, "stage times {times:?}");
+    }
+}
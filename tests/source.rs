@@ -0,0 +1,133 @@
+mod common;
+
+use std::{cell::RefCell, f64::consts::PI, rc::Rc};
+
+use faer_core::{MatMut, MatRef};
+
+use conlaw::{
+    bc, cl, methods, BoundaryCondition, Ctx, Domain, Method, Problem, Resolution, Simulation,
+    Splitting,
+};
+
+use common::{l1_error, orders, run};
+
+/// L1 error of `method` on `u_t + u_x = -u` from `sin(2πx)` on the periodic domain `[0, 1]`,
+/// until `t = 1`, at the Courant number `cfl`
+fn decay_error<M: Method<f64>>(
+    method: M,
+    splitting: Splitting,
+    space_steps: usize,
+    cfl: f64,
+) -> f64 {
+    let problem = Problem::new(
+        "decay",
+        cl::Scalar::new(|u: f64| u),
+        Domain {
+            time: (0., 1.),
+            space: (0., 1.),
+        },
+        bc::Periodic,
+        |x, mut v| v[(0, 0)] = (2. * PI * x).sin(),
+    )
+    .with_source(|u: MatRef<f64>, _, _, mut s: MatMut<f64>| s[(0, 0)] = -u[(0, 0)]);
+    let time_steps = (space_steps as f64 / cfl).ceil() as usize;
+    let sim = Simulation::new(problem)
+        .with_method_instance(method)
+        .with_splitting(splitting)
+        .with_time_resolution(Resolution::Steps(time_steps))
+        .with_space_resolution(Resolution::Steps(space_steps));
+
+    let u = run(sim);
+    let exact: Vec<f64> = (0..=space_steps)
+        .map(|i| (-1f64).exp() * (2. * PI * i as f64 / space_steps as f64).sin())
+        .collect();
+    l1_error(&u, &exact)
+}
+
+fn decay_orders<M: Method<f64>>(
+    method: impl Fn() -> M,
+    splitting: Splitting,
+    cfl: f64,
+) -> Vec<f64> {
+    let errors: Vec<f64> = [20, 40, 80]
+        .map(|n| decay_error(method(), splitting, n, cfl))
+        .into();
+    orders(&errors)
+}
+
+#[test]
+fn unsplit_in_the_stages() {
+    // the source follows the third-order integrator of WENO5 rather than a forward Euler step
+    let orders = decay_orders(methods::Weno5::<f64>::default, Splitting::Unsplit, 0.4);
+    assert!(orders.iter().all(|&p| p > 2.7), "orders {orders:?}");
+}
+
+#[test]
+fn strang() {
+    // second order with a second-order method, against first order unsplit
+    let strang = decay_orders(methods::LaxWendroff::<f64>::default, Splitting::Strang, 0.5);
+    assert!(strang.iter().all(|&p| p > 1.9), "Strang: orders {strang:?}");
+    let unsplit = decay_orders(
+        methods::LaxWendroff::<f64>::default,
+        Splitting::Unsplit,
+        0.5,
+    );
+    assert!(
+        unsplit.iter().all(|&p| p < 1.5),
+        "unsplit: orders {unsplit:?}"
+    );
+}
+
+/// Outflow boundary condition recording, at each call, whether the solution of its context is
+/// the one whose ghost cells it fills
+struct Consistency(Rc<RefCell<Vec<bool>>>);
+
+impl BoundaryCondition<f64> for Consistency {
+    fn apply(
+        &self,
+        ctx: Ctx<f64>,
+        mut left: MatMut<f64>,
+        center: MatRef<f64>,
+        mut right: MatMut<f64>,
+    ) {
+        // the cell i of the left-shifted solution is the cell i - 1 of the solution
+        let shifted = ctx.left();
+        let n = center.nrows();
+        let same = (0..n - 1).all(|i| shifted.read(i + 1, 0) == center.read(i, 0));
+        self.0.borrow_mut().push(same);
+
+        left.fill(center.read(0, 0));
+        right.fill(center.read(n - 1, 0));
+    }
+}
+
+#[test]
+fn strang_boundary_context() {
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let problem = Problem::new(
+        "growth",
+        cl::Scalar::new(|u: f64| u),
+        Domain {
+            time: (0., 1.),
+            space: (0., 1.),
+        },
+        Consistency(calls.clone()),
+        |x, mut v| v[(0, 0)] = x,
+    )
+    .with_source(|_: MatRef<f64>, _, _, mut s: MatMut<f64>| s[(0, 0)] = 1.);
+    let sim = Simulation::new(problem)
+        .with_method::<methods::LaxWendroff<_>>()
+        .with_splitting(Splitting::Strang)
+        .with_time_resolution(Resolution::Steps(4))
+        .with_space_resolution(Resolution::Steps(10));
+    run(sim);
+
+    // the initial condition, then at each step the ghost cells after the first half step of
+    // source, seen with the updated solution, and at the end of the step
+    let calls = calls.borrow();
+    assert_eq!(calls.len(), 1 + 2 * 4);
+    assert!(calls[0]);
+    for step in 0..4 {
+        assert!(calls[1 + 2 * step], "step {step}");
+    }
+}
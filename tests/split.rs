@@ -0,0 +1,89 @@
+mod common;
+
+use faer_core::{MatMut, MatRef};
+
+use conlaw::{bc, cl, methods, BoundaryCondition, Domain, Problem, Resolution, Simulation};
+
+use common::{assert_cell, assert_close, ghost_cells, run};
+
+/// Problem on `[0, 1]` with initial data `(x, 2x)`, to inspect the ghost cells of `bc`
+fn problem<'a>(bc: impl BoundaryCondition<f64> + 'a) -> Problem<'a, f64> {
+    Problem::new(
+        "split",
+        cl::General::new(2, |u: MatRef<f64>, mut v: MatMut<f64>| v.clone_from(u)),
+        Domain {
+            time: (0., 1.),
+            space: (0., 1.),
+        },
+        bc,
+        |x, mut v| {
+            v[(0, 0)] = x;
+            v[(1, 0)] = 2. * x;
+        },
+    )
+}
+
+fn inflow(t: f64, mut v: MatMut<f64>) {
+    v[(0, 0)] = t;
+    v[(1, 0)] = -1.;
+}
+
+#[test]
+fn inflow_and_outflow() {
+    let bc = bc::Split::new(bc::Inflow::new(inflow), bc::Outflow::constant());
+    let ghosts = ghost_cells(problem(bc), 2, 10);
+    assert_eq!(ghosts.len(), 2);
+    for step in &ghosts {
+        for k in 0..2 {
+            assert_cell(&step.left[k], &[step.t, -1.]);
+            assert_cell(&step.right[k], &[1., 2.]);
+        }
+    }
+    // the inflow value follows the time at which ghost cells are filled
+    assert_eq!(ghosts[1].t, 0.5);
+}
+
+#[test]
+fn same_as_dirichlet() {
+    let outflow = |t: f64, mut v: MatMut<f64>| {
+        v[(0, 0)] = 1. - t;
+        v[(1, 0)] = 3.;
+    };
+    let split = ghost_cells(
+        problem(bc::Split::new(
+            bc::Inflow::new(inflow),
+            bc::Inflow::new(outflow),
+        )),
+        2,
+        10,
+    );
+    let dirichlet = ghost_cells(problem(bc::Dirichlet::new(inflow, outflow)), 2, 10);
+    assert_eq!(split, dirichlet);
+}
+
+#[test]
+fn advection_through_domain() {
+    // a step entering on the left while a pulse leaves on the right without reflection
+    let problem = Problem::new(
+        "split_advection",
+        cl::Scalar::new(|u: f64| u),
+        Domain {
+            time: (0., 0.5),
+            space: (0., 1.),
+        },
+        bc::Split::new(
+            bc::Inflow::new(|_, mut v: MatMut<f64>| v[(0, 0)] = 1.),
+            bc::Outflow::constant(),
+        ),
+        |x, mut v| v[(0, 0)] = (-((x - 0.7) / 0.05).powi(2)).exp(),
+    );
+
+    let sim = Simulation::new(problem)
+        .with_method::<methods::LaxWendroff<_>>()
+        .with_time_resolution(Resolution::Steps(200))
+        .with_space_resolution(Resolution::Steps(100));
+
+    let u = run(sim);
+    assert_close(&u, 0..20, 1., 0.05);
+    assert_close(&u, 70..101, 0., 0.01);
+}
@@ -0,0 +1,141 @@
+use conlaw::{
+    bc, cl, methods, Csff1Writer, Domain, Driver, ObsCtx, Observer, Problem, Resolution, SimError,
+    Simulation, TimeStepping,
+};
+
+/// Step, time and time step of every sample, the initial condition included
+struct Samples<'a>(&'a mut Vec<(usize, f64, f64)>);
+
+impl Observer<f64> for Samples<'_> {
+    fn at_startup(&mut self, ctx: ObsCtx<f64>) -> Result<(), SimError> {
+        self.at_each_iteration(ctx)
+    }
+
+    fn at_each_iteration(&mut self, ctx: ObsCtx<f64>) -> Result<(), SimError> {
+        self.0.push((ctx.iter(), ctx.time(), ctx.time_step()));
+        Ok(())
+    }
+}
+
+/// Burgers' equation on `[0, 1]` until `t = 0.2`, from a hump whose top moves at speed `top`
+fn burgers(top: f64) -> Problem<'static, f64> {
+    Problem::new(
+        "burgers",
+        cl::Scalar::new(|u: f64| 0.5 * u * u),
+        Domain {
+            time: (0., 0.2),
+            space: (0., 1.),
+        },
+        bc::Periodic,
+        move |x, mut v| v[(0, 0)] = top * (-100. * (x - 0.5) * (x - 0.5)).exp(),
+    )
+}
+
+fn samples(driver: Driver<f64, methods::LaxFriedrichs<f64>>) -> Vec<(usize, f64, f64)> {
+    let mut samples = Vec::new();
+    driver
+        .with_observer(Samples(&mut samples))
+        .run()
+        .expect("failed to run simulation");
+    samples
+}
+
+fn adaptive(problem: Problem<f64>) -> Driver<f64, methods::LaxFriedrichs<f64>> {
+    let sim = Simulation::new(problem)
+        .with_method::<methods::LaxFriedrichs<_>>()
+        .with_time_resolution(Resolution::Steps(10))
+        .with_space_resolution(Resolution::Steps(100))
+        .with_time_stepping(TimeStepping::Cfl(0.5));
+    Driver::new(sim).with_time_sampling(Resolution::Steps(1))
+}
+
+#[test]
+fn cfl_time_steps() {
+    let samples = samples(adaptive(burgers(1.)));
+
+    // Δt = 0.5 Δx / max|u| while the hump keeps its height, then the last step lands on the end
+    let &(_, _, first) = samples.first().unwrap();
+    assert!((first - 0.005).abs() < 1e-6, "first time step {first}");
+    for w in samples.windows(2) {
+        assert_eq!(w[1].0, w[0].0 + 1);
+        assert!((w[1].1 - (w[0].1 + w[1].2)).abs() < 1e-15);
+    }
+    let &(_, t, dt) = samples.last().unwrap();
+    assert_eq!(t, 0.2);
+    assert!(dt > 0. && dt <= 0.02);
+}
+
+#[test]
+fn zero_wave_speed() {
+    // no wave speed at all: nominal time steps instead of a single step over the whole domain
+    let samples = samples(adaptive(burgers(0.)));
+    assert_eq!(samples.len(), 11);
+    for &(n, t, dt) in &samples[1..] {
+        assert!((dt - 0.02).abs() < 1e-15);
+        assert!((t - 0.02 * n as f64).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn non_finite_wave_speed() {
+    let error = adaptive(burgers(f64::NAN)).run().unwrap_err();
+    assert!(
+        matches!(error, SimError::InvalidWaveSpeed { iter: 0, .. }),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn last_step_sampled() {
+    let sim = Simulation::new(burgers(1.))
+        .with_method::<methods::LaxFriedrichs<_>>()
+        .with_time_resolution(Resolution::Steps(25))
+        .with_space_resolution(Resolution::Steps(100));
+    let samples = samples(Driver::new(sim).with_time_sampling(Resolution::Steps(10)));
+    let steps: Vec<usize> = samples.iter().map(|&(n, _, _)| n).collect();
+    assert_eq!(steps, [0, 10, 20, 25]);
+}
+
+#[test]
+fn sampling_on_actual_time() {
+    let samples = samples(adaptive(burgers(1.)).with_time_sampling(Resolution::Delta(0.05)));
+
+    // the step ending closest to each multiple of the period, and the last one
+    assert_eq!(samples.len(), 5);
+    for (k, &(_, t, dt)) in samples.iter().enumerate() {
+        let target = 0.05 * k as f64;
+        assert!(
+            (t - target).abs() <= 0.5 * dt + 1e-12,
+            "sample {k} at t = {t}"
+        );
+    }
+}
+
+#[test]
+fn csff1_sample_times() {
+    let mut output = Vec::new();
+    let mut times = Vec::new();
+    adaptive(burgers(1.))
+        .with_time_sampling(Resolution::Delta(0.05))
+        .with_observer(Csff1Writer::new(&mut output))
+        .with_observer(Samples(&mut times))
+        .run()
+        .expect("failed to run simulation");
+
+    let name = "Lax-Friedrichs";
+    let header = 5 + 1 + 5 * 4 + 4 * 8 + 4 + name.len() + 4;
+    assert_eq!(&output[..5], b"CSFF1");
+    assert_eq!(
+        &output[header - 4 - name.len()..header - 4],
+        name.as_bytes()
+    );
+    assert_eq!(&output[output.len() - 4..], [0xFF; 4]);
+
+    // each record starts with its time, followed by the 101 cells
+    let records: Vec<&[u8]> = output[header..output.len() - 4].chunks(8 * 102).collect();
+    assert_eq!(records.len(), times.len());
+    for (record, &(_, t, _)) in records.iter().zip(&times) {
+        assert_eq!(record.len(), 8 * 102);
+        assert_eq!(f64::from_ne_bytes(record[..8].try_into().unwrap()), t);
+    }
+}
@@ -0,0 +1,59 @@
+use conlaw::{
+    bc, cl, methods, Domain, Driver, Method, ObsCtx, Observer, Problem, Resolution, SimError,
+    Simulation,
+};
+
+/// Keeps a copy of the final solution
+struct Final<'a>(&'a mut Vec<f64>);
+
+impl Observer<f64> for Final<'_> {
+    fn at_cleanup(&mut self, ctx: ObsCtx<f64>) -> Result<(), SimError> {
+        let u = ctx.solution();
+        self.0.extend((0..u.nrows()).map(|i| u.read(i, 0)));
+        Ok(())
+    }
+}
+
+#[test]
+fn ghost_cells() {
+    let left = methods::UpwindLeft::<f64>::default();
+    assert_eq!((left.left_ghost_cells(), left.right_ghost_cells()), (1, 0));
+
+    // the upwind neighbour of UpwindRight is on the right
+    let right = methods::UpwindRight::<f64>::default();
+    assert_eq!(
+        (right.left_ghost_cells(), right.right_ghost_cells()),
+        (0, 1)
+    );
+}
+
+#[test]
+fn upwind_right_shift() {
+    // at a Courant number of 1 the step entering from the right moves by one cell per step
+    let problem = Problem::new(
+        "upwind_right_shift",
+        cl::Scalar::new(|u: f64| -u),
+        Domain {
+            time: (0., 0.5),
+            space: (0., 1.),
+        },
+        bc::Dirichlet::new(|_, mut v| v[(0, 0)] = 0., |_, mut v| v[(0, 0)] = 1.),
+        |_, mut v| v[(0, 0)] = 0.,
+    );
+
+    let sim = Simulation::new(problem)
+        .with_method::<methods::UpwindRight<_>>()
+        .with_time_resolution(Resolution::Steps(50))
+        .with_space_resolution(Resolution::Steps(100));
+
+    let mut solution = Vec::new();
+    Driver::new(sim)
+        .with_observer(Final(&mut solution))
+        .run()
+        .expect("failed to run simulation");
+
+    for (i, u) in solution.iter().enumerate() {
+        let exact = if i > 50 { 1. } else { 0. };
+        assert!((u - exact).abs() < 1e-12, "u[{i}] = {u} instead of {exact}");
+    }
+}
@@ -0,0 +1,154 @@
+use faer_core::{Mat, MatMut, MatRef};
+
+use conlaw::{cl, ConservationLaw};
+
+/// `p_t + c² v_x = 0`, `v_t + p_x = 0`, with waves of speeds `±c`
+fn acoustics(c: f64) -> impl ConservationLaw<f64> {
+    cl::General::new(2, move |u: MatRef<f64>, mut v: MatMut<f64>| {
+        v[(0, 0)] = c * c * u[(1, 0)];
+        v[(1, 0)] = u[(0, 0)];
+    })
+}
+
+fn state(u: &[f64]) -> Mat<f64> {
+    Mat::from_fn(u.len(), 1, |i, _| u[i])
+}
+
+fn assert_near(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-6, "{a} instead of {b}");
+}
+
+#[test]
+fn jacobian() {
+    let burgers = cl::General::new(1, |u: MatRef<f64>, mut v: MatMut<f64>| {
+        v[(0, 0)] = u[(0, 0)] * u[(0, 0)] / 2.
+    });
+    let mut jac = Mat::<f64>::zeros(1, 1);
+    burgers.jacobian(state(&[3.]).as_ref(), jac.as_mut());
+    assert_near(jac.read(0, 0), 3.);
+    assert_near(burgers.max_wave_speed(state(&[-2.]).as_ref()), 2.);
+}
+
+#[test]
+fn eigenvalues_increasing() {
+    let mut values = Mat::<f64>::zeros(2, 1);
+    acoustics(2.).eigenvalues(state(&[1., 1.]).as_ref(), values.as_mut());
+    assert_near(values.read(0, 0), -2.);
+    assert_near(values.read(1, 0), 2.);
+    assert_near(acoustics(2.).max_wave_speed(state(&[1., 1.]).as_ref()), 2.);
+}
+
+#[test]
+fn eigen_decomposition() {
+    let cl = acoustics(2.);
+    let u = state(&[1., 1.]);
+    let eigen = cl
+        .eigen_decomposition(u.as_ref())
+        .expect("acoustics is hyperbolic");
+
+    // R diag(λ) L reconstructs the Jacobian, and L R = I
+    let mut jac = Mat::<f64>::zeros(2, 2);
+    cl.jacobian(u.as_ref(), jac.as_mut());
+    for i in 0..2 {
+        for j in 0..2 {
+            let rebuilt: f64 = (0..2)
+                .map(|k| eigen.right.read(i, k) * eigen.values.read(k, 0) * eigen.left.read(k, j))
+                .sum();
+            assert_near(rebuilt, jac.read(i, j));
+            let identity: f64 = (0..2)
+                .map(|k| eigen.left.read(i, k) * eigen.right.read(k, j))
+                .sum();
+            assert_near(identity, if i == j { 1. } else { 0. });
+        }
+    }
+}
+
+#[test]
+fn complex_eigenvalues() {
+    // rotation, whose eigenvalues ±i are not real
+    let rotation = cl::General::new(2, |u: MatRef<f64>, mut v: MatMut<f64>| {
+        v[(0, 0)] = -u[(1, 0)];
+        v[(1, 0)] = u[(0, 0)];
+    });
+    let u = state(&[1., 1.]);
+
+    let mut values = Mat::<f64>::zeros(2, 1);
+    rotation.eigenvalues(u.as_ref(), values.as_mut());
+    assert!(values.read(0, 0).is_nan() && values.read(1, 0).is_nan());
+    assert!(rotation.eigen_decomposition(u.as_ref()).is_none());
+    assert!(rotation.max_wave_speed(u.as_ref()).is_nan());
+
+    let bulk = state(&[1., 1., 2., 2.]);
+    assert!(rotation.bulk_max_wave_speed(bulk.as_ref()).is_nan());
+}
+
+#[test]
+fn not_diagonalizable() {
+    // Jordan block, with a double eigenvalue but a single eigenvector
+    let jordan = cl::General::new(2, |u: MatRef<f64>, mut v: MatMut<f64>| {
+        v[(0, 0)] = u[(0, 0)] + u[(1, 0)];
+        v[(1, 0)] = u[(1, 0)];
+    });
+    let u = state(&[1., 1.]);
+
+    let mut values = Mat::<f64>::zeros(2, 1);
+    jordan.eigenvalues(u.as_ref(), values.as_mut());
+    assert_near(values.read(0, 0), 1.);
+    assert_near(values.read(1, 0), 1.);
+    assert!(jordan.eigen_decomposition(u.as_ref()).is_none());
+}
+
+#[test]
+fn bulk_max_wave_speed() {
+    let burgers = cl::General::new(1, |u: MatRef<f64>, mut v: MatMut<f64>| {
+        v[(0, 0)] = u[(0, 0)] * u[(0, 0)] / 2.
+    });
+    let u = state(&[0.5, -3., 2.]);
+    assert_near(burgers.bulk_max_wave_speed(u.as_ref()), 3.);
+}
+
+#[test]
+fn euler_by_finite_differences() {
+    // three distinct waves u - c, u, u + c
+    let euler = cl::Euler::<f64>::default();
+    let general = cl::General::new(3, move |u: MatRef<f64>, v: MatMut<f64>| {
+        euler.flux_function(u, v)
+    });
+    let u = state(&[1., 0.5, 2.5]);
+
+    let (mut exact, mut values) = (Mat::<f64>::zeros(3, 1), Mat::<f64>::zeros(3, 1));
+    euler.eigenvalues(u.as_ref(), exact.as_mut());
+    general.eigenvalues(u.as_ref(), values.as_mut());
+    for i in 0..3 {
+        assert_near(values.read(i, 0), exact.read(i, 0));
+    }
+
+    let eigen = general
+        .eigen_decomposition(u.as_ref())
+        .expect("the Euler equations are hyperbolic");
+    let mut jac = Mat::<f64>::zeros(3, 3);
+    general.jacobian(u.as_ref(), jac.as_mut());
+    for k in 0..3 {
+        // J r = λ r
+        for i in 0..3 {
+            let jr: f64 = (0..3)
+                .map(|j| jac.read(i, j) * eigen.right.read(j, k))
+                .sum();
+            assert_near(jr, eigen.values.read(k, 0) * eigen.right.read(i, k));
+        }
+    }
+}
+
+#[test]
+fn euler_vacuum() {
+    // the vacuum is at rest, and its waves too
+    let euler = cl::Euler::<f64>::default();
+    let mut values = Mat::<f64>::zeros(3, 1);
+    euler.eigenvalues(state(&[0., 0., 0.]).as_ref(), values.as_mut());
+    for i in 0..3 {
+        assert_eq!(values.read(i, 0), 0.);
+    }
+    let u = state(&[1., 0.5, 2.5, 0., 0., 0.]);
+    let c = (1.4f64 * 0.4 * (2.5 - 0.125)).sqrt();
+    assert_near(euler.bulk_max_wave_speed(u.as_ref()), 0.5 + c);
+}
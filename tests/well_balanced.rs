@@ -0,0 +1,195 @@
+mod common;
+
+use conlaw::{bc, cl, fluxes, methods, Domain, Problem, Resolution, Simulation};
+
+use common::{component, l1_error, run};
+
+/// Bump of height 0.4 around `x = 0`
+fn bump(x: f64) -> f64 {
+    if x.abs() < 0.2 {
+        0.4 - 2. * x.abs()
+    } else {
+        0.
+    }
+}
+
+/// Shallow water over `bump` between two walls on `[-1, 1]` with 200 cells, from the free surface
+/// `surface(x)` at rest, until `t = time` in `steps` steps
+fn over_bump(surface: impl Fn(f64) -> f64 + 'static, time: f64, steps: usize) -> Vec<f64> {
+    let shallow_water = cl::ShallowWater::new(1.);
+    let problem = Problem::new(
+        "bump",
+        shallow_water,
+        Domain {
+            time: (0., time),
+            space: (-1., 1.),
+        },
+        bc::Reflective::new(2, [1]),
+        move |x, mut v| {
+            v[(0, 0)] = (surface(x) - bump(x)).max(0.);
+            v[(1, 0)] = 0.;
+        },
+    );
+    let sim = Simulation::new(problem)
+        .with_method_instance(methods::HydrostaticReconstruction::new(
+            shallow_water,
+            fluxes::Hll,
+            bump,
+        ))
+        .with_time_resolution(Resolution::Steps(steps))
+        .with_space_resolution(Resolution::Steps(200));
+    run(sim)
+}
+
+#[test]
+fn lake_at_rest() {
+    // submerging the bump, and emerging from the water with dry cells on its top
+    for level in [0.5, 0.3] {
+        let u = over_bump(move |_| level, 2., 400);
+        for (i, cell) in u.chunks(2).enumerate() {
+            let (x, h) = (-1. + 0.01 * i as f64, cell[0]);
+            if h > 0. {
+                assert!(
+                    (h + bump(x) - level).abs() < 1e-14,
+                    "surface {}",
+                    h + bump(x)
+                );
+            } else {
+                assert!(bump(x) >= level, "dry cell at {x}");
+            }
+            assert!(cell[1].abs() < 1e-14, "momentum {}", cell[1]);
+        }
+    }
+}
+
+#[test]
+fn flooding_a_dry_bump() {
+    // a dam break flooding the dry bump, run at a CFL number close to 1
+    let surface = |x: f64| if x < -0.5 { 0.5 } else { 0. };
+    let u = over_bump(surface, 1., 150);
+
+    // the depth stays non-negative, and no water is created where cells dry out: the walls
+    // being on the boundary cells, only half of them is in the domain
+    let depth = component(&u, 2, 0);
+    assert!(depth.iter().all(|&h| h >= 0.), "negative depth");
+    let mass = |h: &[f64]| h.iter().sum::<f64>() - 0.5 * (h[0] + h[200]);
+    let initial: Vec<f64> = (0..=200)
+        .map(|i| -1. + 0.01 * i as f64)
+        .map(|x| (surface(x) - bump(x)).max(0.))
+        .collect();
+    let (before, after) = (mass(&initial), mass(&depth));
+    assert!(
+        (after - before).abs() < 1e-12 * before,
+        "mass {after} instead of {before}"
+    );
+    // the water went over the bump, not yet far beyond it
+    assert!(depth[120] > 0.01, "depth {} behind the bump", depth[120]);
+    assert_eq!(depth[160], 0.);
+}
+
+#[test]
+fn draining() {
+    // a thin layer of water thrown fast into dry cells: its outgoing fluxes would drain it twice
+    // over, and are scaled down
+    let shallow_water = cl::ShallowWater::new(1.);
+    let problem = Problem::new(
+        "draining",
+        shallow_water,
+        Domain {
+            time: (0., 0.1),
+            space: (0., 1.),
+        },
+        bc::Outflow::constant(),
+        |x, mut v| {
+            let wet = (x - 0.5f64).abs() < 0.01;
+            v[(0, 0)] = if wet { 0.01 } else { 0. };
+            v[(1, 0)] = if wet { 0.1 } else { 0. };
+        },
+    );
+    let sim = Simulation::new(problem)
+        .with_method_instance(methods::HydrostaticReconstruction::new(
+            shallow_water,
+            fluxes::Rusanov,
+            |_| 0.,
+        ))
+        .with_time_resolution(Resolution::Steps(1))
+        .with_space_resolution(Resolution::Steps(20));
+    let depth = component(&run(sim), 2, 0);
+
+    assert!(depth.iter().all(|&h| h >= 0.), "negative depth {depth:?}");
+    let mass: f64 = depth.iter().sum();
+    assert!((mass - 0.01).abs() < 1e-15, "mass {mass}");
+    assert!(depth[10] < 1e-15, "depth {} left behind", depth[10]);
+}
+
+#[test]
+fn dam_break() {
+    // flat bottom: the scheme reduces to the finite volume scheme of its flux
+    let shallow_water = cl::ShallowWater::new(1.);
+    for right_depth in [0.5, 0.] {
+        let (left, right) = (
+            cl::Flow {
+                depth: 1.,
+                velocity: 0.,
+            },
+            cl::Flow {
+                depth: right_depth,
+                velocity: 0.,
+            },
+        );
+        let problem = Problem::new(
+            "dam_break",
+            shallow_water,
+            Domain {
+                time: (0., 0.25),
+                space: (0., 1.),
+            },
+            bc::Outflow::constant(),
+            move |x, v| shallow_water.conserved(if x < 0.5 { &left } else { &right }, v),
+        );
+        let sim = Simulation::new(problem)
+            .with_method_instance(methods::HydrostaticReconstruction::new(
+                shallow_water,
+                fluxes::Hll,
+                |_| 0.,
+            ))
+            .with_time_resolution(Resolution::Steps(100))
+            .with_space_resolution(Resolution::Steps(200));
+        let depth = component(&run(sim), 2, 0);
+
+        let solution = shallow_water.riemann(&left, &right);
+        let exact: Vec<f64> = (0..=200)
+            .map(|i| solution.sample((i as f64 / 200. - 0.5) / 0.25).depth)
+            .collect();
+        let error = l1_error(&depth, &exact);
+        assert!(
+            error < 1e-2,
+            "error {error} with a depth {right_depth} downstream"
+        );
+        assert!(depth.iter().all(|&h| h >= 0.), "negative depth");
+    }
+}
+
+#[test]
+#[should_panic(expected = "gravity of the law differs")]
+fn other_gravity() {
+    let problem = Problem::new(
+        "lake",
+        cl::ShallowWater::new(9.81),
+        Domain {
+            time: (0., 0.1),
+            space: (0., 1.),
+        },
+        bc::Outflow::constant(),
+        |_, mut v| v[(0, 0)] = 1.,
+    );
+    let sim = Simulation::new(problem)
+        .with_method_instance(methods::HydrostaticReconstruction::new(
+            cl::ShallowWater::new(1.),
+            fluxes::Hll,
+            |_| 0.,
+        ))
+        .with_time_resolution(Resolution::Steps(10))
+        .with_space_resolution(Resolution::Steps(10));
+    run(sim);
+}
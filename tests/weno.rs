@@ -0,0 +1,93 @@
+mod common;
+
+use std::f64::consts::PI;
+
+use conlaw::{
+    bc, cl,
+    methods::{self, Integrator},
+    Domain, Problem, Resolution, Simulation,
+};
+
+use common::{advection_orders, orders, run};
+
+/// Largest error of the WENO5 approximation of `-u'` for `u = sin(2πx)` on `space_steps` cells,
+/// recovered from one forward Euler step of linear advection
+fn derivative_error(space_steps: usize) -> f64 {
+    let dt = 1e-3;
+    let problem = Problem::new(
+        "smooth_advection",
+        cl::Scalar::new(|u: f64| u),
+        Domain {
+            time: (0., dt),
+            space: (0., 1.),
+        },
+        bc::Periodic,
+        |x, mut v| v[(0, 0)] = (2. * PI * x).sin(),
+    );
+    let sim = Simulation::new(problem)
+        .with_method_instance(
+            methods::Weno5::<f64>::default().with_integrator(Integrator::ForwardEuler),
+        )
+        .with_time_resolution(Resolution::Steps(1))
+        .with_space_resolution(Resolution::Steps(space_steps));
+
+    run(sim)
+        .into_iter()
+        .enumerate()
+        .map(|(i, u)| {
+            let x = i as f64 / space_steps as f64;
+            let rhs = (u - (2. * PI * x).sin()) / dt;
+            (rhs + 2. * PI * (2. * PI * x).cos()).abs()
+        })
+        .fold(0., f64::max)
+}
+
+#[test]
+fn fifth_order_in_space() {
+    // with the ideal linear weights on smooth data, the reconstruction is fifth-order accurate
+    let errors: Vec<f64> = [10, 20, 40, 80].map(derivative_error).into();
+    let orders = orders(&errors);
+    assert!(orders.iter().all(|&p| p > 4.5), "orders {orders:?}");
+}
+
+#[test]
+fn third_order_in_time() {
+    // at a fixed Courant number, the SSP RK3 integrator limits the order of the full scheme
+    let orders = advection_orders(methods::Weno5::<f64>::default, 20, 0.4);
+    assert!(orders.iter().all(|&p| p > 2.8), "orders {orders:?}");
+}
+
+#[test]
+fn essentially_non_oscillatory() {
+    // the nonlinear weights discard the stencils crossing a discontinuity: a square wave advected
+    // once around the domain keeps a sharp profile with tiny over and undershoots
+    let problem = Problem::new(
+        "square_wave",
+        cl::Scalar::new(|u: f64| u),
+        Domain {
+            time: (0., 1.),
+            space: (0., 1.),
+        },
+        bc::Periodic,
+        |x, mut v| v[(0, 0)] = if (0.25..0.5).contains(&x) { 1. } else { 0. },
+    );
+    let sim = Simulation::new(problem)
+        .with_method::<methods::Weno5<_>>()
+        .with_time_resolution(Resolution::Steps(250))
+        .with_space_resolution(Resolution::Steps(100));
+    let u = run(sim);
+
+    let (min, max) = u
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &u| {
+            (min.min(u), max.max(u))
+        });
+    assert!(min > -1e-2 && max < 1. + 1e-2, "values in [{min}, {max}]");
+    // the plateau and the zero state are kept away from the discontinuities
+    assert!(
+        (u[37] - 1.).abs() < 1e-2 && u[80].abs() < 1e-3,
+        "{} {}",
+        u[37],
+        u[80]
+    );
+}
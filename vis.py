@@ -36,7 +36,9 @@ class CSFF1:
 
 		self.sample_start = self.solution.tell()
 		self.samples_length= self.space_steps//self.space_sampling + 1
-		self.num_samples = self.time_steps//self.time_sampling + 1
+		# the number of steps is only nominal with adaptive time stepping, count samples instead
+		self.sample_size = self.float_size*(1 + self.system_size*self.samples_length)
+		self.num_samples = (self.file_size - self.sample_start - 4)//self.sample_size
 
 		return self
 
@@ -48,7 +50,7 @@ CSFF1 reader:
 	file size: {humanize.naturalsize(self.file_size, binary=True)} 
 	output file name: `{file}.gif`
 	spatial grid: [{self.space_lower}, {self.space_upper}], Δx = {(self.space_upper-self.space_lower)/self.space_steps} ({self.space_steps} steps)
-	temporal grid: [{self.time_lower}, {self.time_upper}], Δt = {(self.time_upper-self.time_lower)/self.time_steps} ({self.time_steps} steps)
+	temporal grid: [{self.time_lower}, {self.time_upper}], nominal Δt = {(self.time_upper-self.time_lower)/self.time_steps} ({self.time_steps} steps)
 	time sampling period: {self.time_sampling}
 	space sampling period: {self.space_sampling}
 	floating-point precision: {self.float_size*8} bits
@@ -56,10 +58,13 @@ CSFF1 reader:
 
 
 	def samples(self):
+		"""Time and solution of each sample"""
 		self.solution.seek(self.sample_start)
 
 		for _ in range(self.num_samples):
-			yield np.frombuffer(self.solution.read(self.float_size*self.system_size*self.samples_length), self.dt)
+			sample = np.frombuffer(self.solution.read(self.sample_size), self.dt)
+			# one column per component of the system
+			yield sample[0], sample[1:].reshape(self.samples_length, self.system_size)
 
 		assert self.solution.read(4) == b"\xff\xff\xff\xff"
 		
@@ -77,13 +82,19 @@ def render(input: CSFF1):
 	samples = input.samples()
 
 	fig, ax = plt.subplots()
-	u = next(samples)
-	handle = ax.plot(xs, u)[0]
-	ax.set(ylim=[np.floor(np.min(u)), np.ceil(np.max(u))])
+	t, u = next(samples)
+	handles = ax.plot(xs, u)
+	ax.set(ylim=[np.floor(np.min(u)), np.ceil(np.max(u))], title=f"t = {t:.4g}")
+
+	def update(frame):
+		t, u = next(samples)
+		for component, handle in enumerate(handles):
+			handle.set_ydata(u[:, component])
+		ax.set_title(f"t = {t:.4g}")
 
 	gif = FuncAnimation(
 		fig=fig, frames=input.num_samples-2,
-		func=lambda frame: handle.set_ydata(next(samples)), 
+		func=update,
 	)
 	gif.save(filename=f"{input.src}.gif", writer="ffmpeg", fps=60)
 